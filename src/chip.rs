@@ -0,0 +1,151 @@
+//! Contains the chip variants supported by the standard expander types.
+//!
+//! The PCA9555, TCA9535 and TCA9555 share the register map, address range and power-on state of the PCA9535. The PCA9539 uses the same register map,
+//! but only provides two hardware address pins in the range of `116-119` and an additional active-low RESET input.
+//!
+//! The 8-bit PCA9534, PCA9554 and TCA9554 provide the registers of [`crate::GPIOBank::Bank0`] only and are used with the [`crate::Pca9554Immediate`] expander.
+//!
+//! The variant of an expander is selected using the `with_chip()` constructors, for example [`crate::Pca9535Immediate::with_chip()`].
+//! The plain `new()` constructors always create an expander for the [`Pca9535`] or [`Pca9554`] respectively.
+use core::fmt::Debug;
+
+use crate::Register;
+
+/// A chip variant of the PCA9535 family.
+///
+/// Implement this trait for further register compatible devices which are not yet covered by this crate.
+pub trait Chip: Debug + Copy {
+    /// Name of the device.
+    const NAME: &'static str;
+    /// Lowest 7-bit I2C address the device can be configured to.
+    const ADDRESS_MIN: u8;
+    /// Highest 7-bit I2C address the device can be configured to.
+    const ADDRESS_MAX: u8;
+    /// Number of GPIO banks of the device. 16-bit expanders provide two banks, 8-bit expanders one.
+    const BANKS: u8 = 2;
+    /// `true` if the device provides a hardware RESET input, which allows to restore the power-on state without power-cycling the device.
+    const HAS_RESET_PIN: bool = false;
+
+    /// Returns `true` if the device can be configured to the given 7-bit I2C address.
+    fn is_valid_address(address: u8) -> bool {
+        (Self::ADDRESS_MIN..=Self::ADDRESS_MAX).contains(&address)
+    }
+
+    /// Returns the value of the given register after power-on or a hardware reset.
+    ///
+    /// The value of the input registers depends on the voltage levels applied to the pins, in which case `0x00` is returned.
+    fn power_on_default(register: Register) -> u8 {
+        match register {
+            Register::InputPort0 | Register::InputPort1 => 0x00,
+            Register::OutputPort0 | Register::OutputPort1 => 0xFF,
+            Register::PolarityInversionPort0 | Register::PolarityInversionPort1 => 0x00,
+            Register::ConfigurationPort0 | Register::ConfigurationPort1 => 0xFF,
+        }
+    }
+}
+
+/// The NXP PCA9535 and PCA9535C
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Pca9535;
+
+impl Chip for Pca9535 {
+    const NAME: &'static str = "PCA9535";
+    const ADDRESS_MIN: u8 = 32;
+    const ADDRESS_MAX: u8 = 39;
+}
+
+/// The NXP PCA9555, which adds internal pull-up resistors to the PCA9535
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Pca9555;
+
+impl Chip for Pca9555 {
+    const NAME: &'static str = "PCA9555";
+    const ADDRESS_MIN: u8 = 32;
+    const ADDRESS_MAX: u8 = 39;
+}
+
+/// The Texas Instruments TCA9535
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Tca9535;
+
+impl Chip for Tca9535 {
+    const NAME: &'static str = "TCA9535";
+    const ADDRESS_MIN: u8 = 32;
+    const ADDRESS_MAX: u8 = 39;
+}
+
+/// The Texas Instruments TCA9555, which adds internal pull-up resistors to the TCA9535
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Tca9555;
+
+impl Chip for Tca9555 {
+    const NAME: &'static str = "TCA9555";
+    const ADDRESS_MIN: u8 = 32;
+    const ADDRESS_MAX: u8 = 39;
+}
+
+/// The NXP PCA9539, featuring an active-low RESET input and the address pins A0 and A1 only
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Pca9539;
+
+impl Chip for Pca9539 {
+    const NAME: &'static str = "PCA9539";
+    const ADDRESS_MIN: u8 = 116;
+    const ADDRESS_MAX: u8 = 119;
+    const HAS_RESET_PIN: bool = true;
+}
+
+/// The NXP PCA9534, an 8-bit expander
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Pca9534;
+
+impl Chip for Pca9534 {
+    const NAME: &'static str = "PCA9534";
+    const ADDRESS_MIN: u8 = 32;
+    const ADDRESS_MAX: u8 = 39;
+    const BANKS: u8 = 1;
+}
+
+/// The NXP PCA9534A, which differs from the PCA9534 in its address range only
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Pca9534a;
+
+impl Chip for Pca9534a {
+    const NAME: &'static str = "PCA9534A";
+    const ADDRESS_MIN: u8 = 56;
+    const ADDRESS_MAX: u8 = 63;
+    const BANKS: u8 = 1;
+}
+
+/// The NXP PCA9554, an 8-bit expander with internal pull-up resistors
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Pca9554;
+
+impl Chip for Pca9554 {
+    const NAME: &'static str = "PCA9554";
+    const ADDRESS_MIN: u8 = 32;
+    const ADDRESS_MAX: u8 = 39;
+    const BANKS: u8 = 1;
+}
+
+/// The NXP PCA9554A, which differs from the PCA9554 in its address range only
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Pca9554a;
+
+impl Chip for Pca9554a {
+    const NAME: &'static str = "PCA9554A";
+    const ADDRESS_MIN: u8 = 56;
+    const ADDRESS_MAX: u8 = 63;
+    const BANKS: u8 = 1;
+}
+
+/// The Texas Instruments TCA9554, an 8-bit expander with internal pull-up resistors
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Tca9554;
+
+impl Chip for Tca9554 {
+    const NAME: &'static str = "TCA9554";
+    const ADDRESS_MIN: u8 = 32;
+    const ADDRESS_MAX: u8 = 39;
+    const BANKS: u8 = 1;
+}
@@ -0,0 +1,228 @@
+//! Contains the traits for expanders supporting the extended "Agile I/O" registers of the PCAL9535A.
+use core::fmt::Debug;
+
+use hal::i2c::{ErrorType, I2c};
+
+use super::{check_pin, Expander, ExpanderError, GPIOBank};
+use crate::{DriveStrength, ExtendedRegister, OutputMode, Pull};
+
+/// Trait for expanders which provide the extended "Agile I/O" register set in addition to the standard registers.
+pub trait AgileExpander<I2C>: Expander<I2C>
+where
+    I2C: I2c,
+{
+    fn write_extended_byte(
+        &mut self,
+        register: ExtendedRegister,
+        data: u8,
+    ) -> Result<(), ExpanderError<<I2C as ErrorType>::Error>>;
+
+    fn read_extended_byte(
+        &mut self,
+        register: ExtendedRegister,
+        buffer: &mut u8,
+    ) -> Result<(), ExpanderError<<I2C as ErrorType>::Error>>;
+    fn write_extended_halfword(
+        &mut self,
+        register: ExtendedRegister,
+        data: u16,
+    ) -> Result<(), ExpanderError<<I2C as ErrorType>::Error>>;
+    fn read_extended_halfword(
+        &mut self,
+        register: ExtendedRegister,
+        buffer: &mut u16,
+    ) -> Result<(), ExpanderError<<I2C as ErrorType>::Error>>;
+}
+
+/// Interface to the extended "Agile I/O" features not using [`hal`].
+///
+/// The functions of this interface complement the [`crate::StandardExpanderInterface`], which can be used alongside for all the standard functionality.
+pub trait AgileExpanderInterface<I2C, E>: AgileExpander<I2C>
+where
+    E: Debug,
+    I2C: I2c<Error = E>,
+{
+    /// Sets the output drive strength of the given pin.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
+    fn pin_set_drive_strength(
+        &mut self,
+        bank: GPIOBank,
+        pin: u8,
+        strength: DriveStrength,
+    ) -> Result<(), ExpanderError<E>> {
+        check_pin(pin)?;
+
+        let register = match (bank, pin < 4) {
+            (GPIOBank::Bank0, true) => ExtendedRegister::OutputDriveStrength0A,
+            (GPIOBank::Bank0, false) => ExtendedRegister::OutputDriveStrength0B,
+            (GPIOBank::Bank1, true) => ExtendedRegister::OutputDriveStrength1A,
+            (GPIOBank::Bank1, false) => ExtendedRegister::OutputDriveStrength1B,
+        };
+        let shift = (pin % 4) * 2;
+
+        let mut reg_val: u8 = 0x00;
+
+        self.read_extended_byte(register, &mut reg_val)?;
+
+        self.write_extended_byte(
+            register,
+            (reg_val & !(0x03 << shift)) | ((strength as u8) << shift),
+        )
+    }
+
+    /// Enables the input latch of the given pin.
+    ///
+    /// While latched, an input change which triggered an interrupt is held in the input register until it is read, even if the pin returns to its previous state.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
+    fn pin_enable_input_latch(&mut self, bank: GPIOBank, pin: u8) -> Result<(), ExpanderError<E>> {
+        check_pin(pin)?;
+
+        let register = match bank {
+            GPIOBank::Bank0 => ExtendedRegister::InputLatch0,
+            GPIOBank::Bank1 => ExtendedRegister::InputLatch1,
+        };
+
+        let mut reg_val: u8 = 0x00;
+
+        self.read_extended_byte(register, &mut reg_val)?;
+
+        self.write_extended_byte(register, reg_val | (0x01 << pin))
+    }
+
+    /// Disables the input latch of the given pin.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
+    fn pin_disable_input_latch(&mut self, bank: GPIOBank, pin: u8) -> Result<(), ExpanderError<E>> {
+        check_pin(pin)?;
+
+        let register = match bank {
+            GPIOBank::Bank0 => ExtendedRegister::InputLatch0,
+            GPIOBank::Bank1 => ExtendedRegister::InputLatch1,
+        };
+
+        let mut reg_val: u8 = 0x00;
+
+        self.read_extended_byte(register, &mut reg_val)?;
+
+        self.write_extended_byte(register, reg_val & !(0x01 << pin))
+    }
+
+    /// Configures the internal pull resistor of the given pin.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
+    fn pin_set_pull(
+        &mut self,
+        bank: GPIOBank,
+        pin: u8,
+        pull: Pull,
+    ) -> Result<(), ExpanderError<E>> {
+        check_pin(pin)?;
+
+        let (enable_register, selection_register) = match bank {
+            GPIOBank::Bank0 => (
+                ExtendedRegister::PullEnable0,
+                ExtendedRegister::PullSelection0,
+            ),
+            GPIOBank::Bank1 => (
+                ExtendedRegister::PullEnable1,
+                ExtendedRegister::PullSelection1,
+            ),
+        };
+
+        let mut reg_val: u8 = 0x00;
+
+        if let Pull::Up | Pull::Down = pull {
+            self.read_extended_byte(selection_register, &mut reg_val)?;
+
+            if let Pull::Up = pull {
+                self.write_extended_byte(selection_register, reg_val | (0x01 << pin))?;
+            } else {
+                self.write_extended_byte(selection_register, reg_val & !(0x01 << pin))?;
+            }
+        }
+
+        self.read_extended_byte(enable_register, &mut reg_val)?;
+
+        if let Pull::None = pull {
+            self.write_extended_byte(enable_register, reg_val & !(0x01 << pin))
+        } else {
+            self.write_extended_byte(enable_register, reg_val | (0x01 << pin))
+        }
+    }
+
+    /// Masks the interrupt of the given pin, so input changes of this pin do not assert the interrupt output of the device.
+    ///
+    /// All interrupts are masked on device startup.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
+    fn pin_mask_interrupt(&mut self, bank: GPIOBank, pin: u8) -> Result<(), ExpanderError<E>> {
+        check_pin(pin)?;
+
+        let register = match bank {
+            GPIOBank::Bank0 => ExtendedRegister::InterruptMask0,
+            GPIOBank::Bank1 => ExtendedRegister::InterruptMask1,
+        };
+
+        let mut reg_val: u8 = 0x00;
+
+        self.read_extended_byte(register, &mut reg_val)?;
+
+        self.write_extended_byte(register, reg_val | (0x01 << pin))
+    }
+
+    /// Unmasks the interrupt of the given pin, so input changes of this pin assert the interrupt output of the device.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
+    fn pin_unmask_interrupt(&mut self, bank: GPIOBank, pin: u8) -> Result<(), ExpanderError<E>> {
+        check_pin(pin)?;
+
+        let register = match bank {
+            GPIOBank::Bank0 => ExtendedRegister::InterruptMask0,
+            GPIOBank::Bank1 => ExtendedRegister::InterruptMask1,
+        };
+
+        let mut reg_val: u8 = 0x00;
+
+        self.read_extended_byte(register, &mut reg_val)?;
+
+        self.write_extended_byte(register, reg_val & !(0x01 << pin))
+    }
+
+    /// Returns which pins caused the current interrupt.
+    ///
+    /// The returned mask holds one bit per pin, where bit `n` represents pin `n` of [`GPIOBank::Bank0`] and bit `8 + n` represents pin `n` of [`GPIOBank::Bank1`].
+    /// The interrupt status is cleared by the device once the corresponding input register is read.
+    fn interrupt_status(&mut self) -> Result<u16, ExpanderError<E>> {
+        let mut reg_val: u16 = 0x0000;
+
+        self.read_extended_halfword(ExtendedRegister::InterruptStatus0, &mut reg_val)?;
+
+        Ok(reg_val.swap_bytes())
+    }
+
+    /// Configures the output stage of all pins of the given bank.
+    fn set_output_mode(
+        &mut self,
+        bank: GPIOBank,
+        mode: OutputMode,
+    ) -> Result<(), ExpanderError<E>> {
+        let mut reg_val: u8 = 0x00;
+
+        self.read_extended_byte(ExtendedRegister::OutputPortConfiguration, &mut reg_val)?;
+
+        let reg_val = match mode {
+            OutputMode::PushPull => reg_val & !(0x01 << bank as u8),
+            OutputMode::OpenDrain => reg_val | (0x01 << bank as u8),
+        };
+
+        self.write_extended_byte(ExtendedRegister::OutputPortConfiguration, reg_val)
+    }
+}
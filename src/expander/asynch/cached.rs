@@ -0,0 +1,338 @@
+//! Contains the implementation of the async Cached Expander interface.
+use core::fmt::Debug;
+
+use hal::digital::{Error as _, InputPin};
+use hal_async::i2c::I2c;
+
+use super::standard::AsyncStandardExpanderInterface;
+use super::{AsyncExpander, ExpanderError, Register};
+use crate::chip::{Chip, Pca9535};
+use crate::expander::check_address;
+
+/// Async counterpart of [`crate::Pca9535Cached`].
+///
+/// The interrupt pin is read using the blocking [`InputPin`] trait, as reading a pin level does not need to wait on any peripheral.
+#[derive(Debug)]
+pub struct Pca9535CachedAsync<I2C, IP, C = Pca9535>
+where
+    I2C: I2c,
+    IP: InputPin,
+    C: Chip,
+{
+    address: u8,
+    i2c: I2C,
+    interrupt_pin: IP,
+    chip: C,
+
+    input_port_0: u8,
+    input_port_1: u8,
+    output_port_0: u8,
+    output_port_1: u8,
+    polarity_inversion_port_0: u8,
+    polarity_inversion_port_1: u8,
+    configuration_port_0: u8,
+    configuration_port_1: u8,
+}
+
+impl<I2C, E, IP> Pca9535CachedAsync<I2C, IP>
+where
+    IP: InputPin,
+    E: Debug,
+    I2C: I2c<Error = E>,
+{
+    /// Creates a new async cached PCA9535 instance.
+    ///
+    /// # Cached registers
+    /// The init_defaults argument assumes the default values for all the device registers if set to `true` (Default register condition after device startup; see the device's documentation for more information).
+    /// In that case, no bus transaction is created to verify if this is actually the case on the device. Only use this option if you have not made any transactions with the device before creating this expander struct,
+    /// otherwise you might encounter unexpected behavior of the device!
+    ///
+    /// If the device was used before calling this function and should keep its state, you should set init_defaults to `false`. This triggers a bus transaction to read out all the devices' registers and caches the received values.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidAddress`] if the given device hardware address is outside the permittable range of `32-39`.
+    pub async fn new(
+        i2c: I2C,
+        address: u8,
+        interrupt_pin: IP,
+        init_defaults: bool,
+    ) -> Result<Self, ExpanderError<E>> {
+        Self::with_chip(i2c, address, interrupt_pin, init_defaults, Pca9535).await
+    }
+}
+
+impl<I2C, E, IP, C> Pca9535CachedAsync<I2C, IP, C>
+where
+    IP: InputPin,
+    E: Debug,
+    I2C: I2c<Error = E>,
+    C: Chip,
+{
+    /// Creates a new async cached instance of the given chip variant.
+    ///
+    /// If `init_defaults` is set to `true`, the cache is initialized using the power-on defaults of the chip variant (see [`Chip::power_on_default()`]).
+    /// Please see [`Pca9535CachedAsync::new()`] for more information on the cache initialization.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidAddress`] if the given device hardware address is outside the permittable range of the chip variant.
+    ///
+    /// # Compile errors
+    /// The chip variant needs to be a 16-bit expander (see [`Chip::BANKS`]).
+    pub async fn with_chip(
+        i2c: I2C,
+        address: u8,
+        interrupt_pin: IP,
+        init_defaults: bool,
+        chip: C,
+    ) -> Result<Self, ExpanderError<E>> {
+        const { assert!(C::BANKS == 2, "the chip variant is not a 16-bit expander") };
+
+        check_address::<C, _>(address)?;
+
+        let mut expander = Self {
+            address,
+            i2c,
+            interrupt_pin,
+            chip,
+            input_port_0: C::power_on_default(Register::InputPort0),
+            input_port_1: C::power_on_default(Register::InputPort1),
+            output_port_0: C::power_on_default(Register::OutputPort0),
+            output_port_1: C::power_on_default(Register::OutputPort1),
+            polarity_inversion_port_0: C::power_on_default(Register::PolarityInversionPort0),
+            polarity_inversion_port_1: C::power_on_default(Register::PolarityInversionPort1),
+            configuration_port_0: C::power_on_default(Register::ConfigurationPort0),
+            configuration_port_1: C::power_on_default(Register::ConfigurationPort1),
+        };
+
+        if !init_defaults {
+            expander.init_cache().await?;
+        }
+
+        Ok(expander)
+    }
+
+    /// Returns the chip variant of the expander.
+    pub fn chip(&self) -> C {
+        self.chip
+    }
+
+    /// Destroys the expander struct, returning the contained I2C and interrupt pin
+    pub fn destroy(self) -> (I2C, IP) {
+        (self.i2c, self.interrupt_pin)
+    }
+
+    /// Initializes the device's cache by reading out all the required device registers.
+    async fn init_cache(&mut self) -> Result<(), ExpanderError<E>> {
+        for register in [
+            Register::ConfigurationPort0,
+            Register::InputPort0,
+            Register::OutputPort0,
+            Register::PolarityInversionPort0,
+        ] {
+            let mut buf: [u8; 2] = [0x00, 0x00];
+
+            self.i2c
+                .write_read(self.address, &[register as u8], &mut buf)
+                .await
+                .map_err(ExpanderError::WriteReadError)?;
+
+            self.set_cached(register, buf[0]);
+            self.set_cached(register.get_neighbor(), buf[1]);
+        }
+
+        Ok(())
+    }
+
+    /// Reads both input registers in a single bus transaction and caches their values.
+    ///
+    /// Reading any input register clears the device's interrupt output, so both registers have to be fetched at once to not miss the changes of the other bank.
+    async fn read_inputs_into_cache(&mut self) -> Result<(), ExpanderError<E>> {
+        let mut buf: [u8; 2] = [0x00; 2];
+
+        self.i2c
+            .write_read(self.address, &[Register::InputPort0 as u8], &mut buf)
+            .await
+            .map_err(ExpanderError::WriteReadError)?;
+
+        self.set_cached(Register::InputPort0, buf[0]);
+        self.set_cached(Register::InputPort1, buf[1]);
+
+        Ok(())
+    }
+
+    /// Returns `true` if the interrupt pin is held `low`, indicating a change of the device's input registers.
+    fn interrupt_active(&mut self) -> Result<bool, ExpanderError<E>> {
+        self.interrupt_pin
+            .is_low()
+            .map_err(|err| ExpanderError::InterruptPinError(err.kind()))
+    }
+
+    fn get_cached(&self, register: Register) -> u8 {
+        match register {
+            Register::InputPort0 => self.input_port_0,
+            Register::InputPort1 => self.input_port_1,
+            Register::OutputPort0 => self.output_port_0,
+            Register::OutputPort1 => self.output_port_1,
+            Register::PolarityInversionPort0 => self.polarity_inversion_port_0,
+            Register::PolarityInversionPort1 => self.polarity_inversion_port_1,
+            Register::ConfigurationPort0 => self.configuration_port_0,
+            Register::ConfigurationPort1 => self.configuration_port_1,
+        }
+    }
+
+    fn set_cached(&mut self, register: Register, value: u8) {
+        match register {
+            Register::InputPort0 => self.input_port_0 = value,
+            Register::InputPort1 => self.input_port_1 = value,
+            Register::OutputPort0 => self.output_port_0 = value,
+            Register::OutputPort1 => self.output_port_1 = value,
+            Register::PolarityInversionPort0 => self.polarity_inversion_port_0 = value,
+            Register::PolarityInversionPort1 => self.polarity_inversion_port_1 = value,
+            Register::ConfigurationPort0 => self.configuration_port_0 = value,
+            Register::ConfigurationPort1 => self.configuration_port_1 = value,
+        };
+    }
+
+    /// Flips the cached input bits of the register pair member matching the given polarity inversion register.
+    ///
+    /// The IO Expander does not trigger an interrupt once the polarity inversion register value changes, so the input register cache has to be updated manually.
+    fn apply_polarity_change(&mut self, register: Register, data: u8) {
+        let input_mask = self.get_cached(register) ^ data;
+
+        let input_register = match register {
+            Register::PolarityInversionPort0 => Register::InputPort0,
+            Register::PolarityInversionPort1 => Register::InputPort1,
+            _ => unreachable!(),
+        };
+
+        self.set_cached(input_register, self.get_cached(input_register) ^ input_mask);
+    }
+}
+
+impl<I2C, IP, E, C> AsyncExpander<I2C> for Pca9535CachedAsync<I2C, IP, C>
+where
+    IP: InputPin,
+    I2C: I2c<Error = E>,
+    E: Debug,
+    C: Chip,
+{
+    /// Writes one byte to the given register
+    ///
+    /// Only use this function if you really have to. For most use cases, the crate provides simpler ways of interacting with the device.
+    ///
+    /// # Cached
+    /// If the bus write succeeds, the written data is cached to avoid the need for bus traffic upon reading the written register.
+    async fn write_byte(&mut self, register: Register, data: u8) -> Result<(), ExpanderError<E>> {
+        self.i2c
+            .write(self.address, &[register as u8, data])
+            .await
+            .map_err(ExpanderError::WriteError)?;
+
+        if register.is_polarity_inversion() {
+            self.apply_polarity_change(register, data);
+        }
+
+        self.set_cached(register, data);
+        Ok(())
+    }
+
+    /// Reads one byte of the given register
+    ///
+    /// Only use this function if you really have to. For most use cases, the crate provides simpler ways of interacting with the device.
+    ///
+    /// # Cached
+    /// This function only creates bus traffic if the provided interrupt pin is held at a `low` voltage level at the time of the function call and the provided register is an input register.
+    /// In that case, both input registers are read from the device in a single bus transaction, as the device's interrupt output indicates a data change.
+    /// Otherwise the cached value is returned without causing any bus traffic.
+    async fn read_byte(
+        &mut self,
+        register: Register,
+        buffer: &mut u8,
+    ) -> Result<(), ExpanderError<E>> {
+        if register.is_input() && self.interrupt_active()? {
+            self.read_inputs_into_cache().await?;
+        }
+
+        *buffer = self.get_cached(register);
+
+        Ok(())
+    }
+
+    /// Writes one half-word to the given register
+    ///
+    /// Only use this function if you really have to. For most use cases, the crate provides simpler ways of interacting with the device.
+    ///
+    /// # Register pairs
+    /// Please see [`Register`] for more information about the register pairs and how they affect the half-word read and write functions.
+    ///
+    /// # Cached
+    /// If the bus write succeeds, the written data is cached to avoid the need for bus traffic upon reading the written register.
+    async fn write_halfword(
+        &mut self,
+        register: Register,
+        data: u16,
+    ) -> Result<(), ExpanderError<E>> {
+        self.i2c
+            .write(
+                self.address,
+                &[register as u8, (data >> 8) as u8, data as u8],
+            )
+            .await
+            .map_err(ExpanderError::WriteError)?;
+
+        if register.is_polarity_inversion() {
+            self.apply_polarity_change(register, (data >> 8) as u8);
+            self.apply_polarity_change(register.get_neighbor(), data as u8);
+        }
+
+        self.set_cached(register, (data >> 8) as u8);
+        self.set_cached(register.get_neighbor(), data as u8);
+
+        Ok(())
+    }
+
+    /// Reads one half-word of the given register
+    ///
+    /// Only use this function if you really have to. For most use cases, the crate provides simpler ways of interacting with the device.
+    ///
+    /// # Register pairs
+    /// Please see [`Register`] for more information about the register pairs and how they affect the half-word read and write functions.
+    ///
+    /// # Cached
+    /// This function only creates bus traffic in case the provided interrupt pin is held at a `low` voltage level at the time of the function call and the provided
+    /// register is an input register. In that case, the data is being read from the device, as the device's interrupt output indicates a data change.
+    /// Otherwise, the cached value is returned without causing any bus traffic.
+    async fn read_halfword(
+        &mut self,
+        register: Register,
+        buffer: &mut u16,
+    ) -> Result<(), ExpanderError<E>> {
+        let mut reg_val: [u8; 2] = [0x00; 2];
+
+        if register.is_input() && self.interrupt_active()? {
+            self.i2c
+                .write_read(self.address, &[register as u8], &mut reg_val)
+                .await
+                .map_err(ExpanderError::WriteReadError)?;
+
+            self.set_cached(register, reg_val[0]);
+            self.set_cached(register.get_neighbor(), reg_val[1]);
+
+            *buffer = (reg_val[0] as u16) << 8 | reg_val[1] as u16;
+        } else {
+            *buffer = (self.get_cached(register) as u16) << 8
+                | self.get_cached(register.get_neighbor()) as u16;
+        }
+
+        Ok(())
+    }
+}
+
+impl<I2C, E, IP, C> AsyncStandardExpanderInterface<I2C, E> for Pca9535CachedAsync<I2C, IP, C>
+where
+    IP: InputPin,
+    E: Debug,
+    I2C: I2c<Error = E>,
+    C: Chip,
+{
+}
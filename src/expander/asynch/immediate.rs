@@ -0,0 +1,173 @@
+//! Contains the implementation of the async Immediate Expander interface.
+use core::fmt::Debug;
+
+use hal_async::i2c::I2c;
+
+use super::standard::AsyncStandardExpanderInterface;
+use super::{AsyncExpander, ExpanderError, Register};
+use crate::chip::{Chip, Pca9535};
+use crate::expander::check_address;
+use crate::HardwareAddress;
+
+/// Async counterpart of [`crate::Pca9535Immediate`].
+///
+/// Issues an i2c bus transaction on each function call without blocking the executor while waiting for the bus.
+#[derive(Debug)]
+pub struct Pca9535ImmediateAsync<I2C, C = Pca9535>
+where
+    I2C: I2c,
+    C: Chip,
+{
+    address: u8,
+    i2c: I2C,
+    chip: C,
+}
+
+impl<I2C> Pca9535ImmediateAsync<I2C>
+where
+    I2C: I2c,
+{
+    /// Creates a new async immediate PCA9535 instance.
+    ///
+    /// # Panics
+    /// If the given device hardware address is outside the permittable range of `32-39`.
+    /// Use [`Pca9535ImmediateAsync::from_hardware_address()`] to create the instance from an address which is guaranteed to be valid, or [`Pca9535ImmediateAsync::try_new()`] to handle an invalid address as error.
+    pub fn new(i2c: I2C, address: u8) -> Self {
+        assert!(Pca9535::is_valid_address(address));
+
+        Self {
+            address,
+            i2c,
+            chip: Pca9535,
+        }
+    }
+
+    /// Creates a new async immediate PCA9535 instance, returning [`ExpanderError::InvalidAddress`] instead of panicking if the given device hardware address is outside the permittable range of `32-39`.
+    pub fn try_new(i2c: I2C, address: u8) -> Result<Self, ExpanderError<I2C::Error>> {
+        Self::with_chip(i2c, address, Pca9535)
+    }
+
+    /// Creates a new async immediate PCA9535 instance using the given [`HardwareAddress`], which is always within the permittable range.
+    pub fn from_hardware_address(i2c: I2C, address: HardwareAddress) -> Self {
+        Self::new(i2c, address.address())
+    }
+}
+
+impl<I2C, C> Pca9535ImmediateAsync<I2C, C>
+where
+    I2C: I2c,
+    C: Chip,
+{
+    /// Creates a new async immediate instance of the given chip variant.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidAddress`] if the given device hardware address is outside the permittable range of the chip variant.
+    ///
+    /// # Compile errors
+    /// The chip variant needs to be a 16-bit expander (see [`Chip::BANKS`]).
+    pub fn with_chip(i2c: I2C, address: u8, chip: C) -> Result<Self, ExpanderError<I2C::Error>> {
+        const { assert!(C::BANKS == 2, "the chip variant is not a 16-bit expander") };
+
+        check_address::<C, _>(address)?;
+
+        Ok(Self { address, i2c, chip })
+    }
+
+    /// Returns the chip variant of the expander.
+    pub fn chip(&self) -> C {
+        self.chip
+    }
+
+    /// Destroys the expander struct, returning the contained I2C
+    pub fn destroy(self) -> I2C {
+        self.i2c
+    }
+}
+
+impl<I2C, E, C> AsyncExpander<I2C> for Pca9535ImmediateAsync<I2C, C>
+where
+    E: Debug,
+    I2C: I2c<Error = E>,
+    C: Chip,
+{
+    /// Writes one byte to the given register
+    ///
+    /// Only use this function if you really have to. For most use cases, the crate provides simpler ways of interacting with the device.
+    async fn write_byte(&mut self, register: Register, data: u8) -> Result<(), ExpanderError<E>> {
+        self.i2c
+            .write(self.address, &[register as u8, data])
+            .await
+            .map_err(ExpanderError::WriteError)
+    }
+
+    /// Reads one byte of the given register
+    ///
+    /// Only use this function if you really have to. For most use cases, the crate provides simpler ways of interacting with the device.
+    async fn read_byte(
+        &mut self,
+        register: Register,
+        buffer: &mut u8,
+    ) -> Result<(), ExpanderError<E>> {
+        let mut buf = [0_u8];
+
+        self.i2c
+            .write_read(self.address, &[register as u8], &mut buf)
+            .await
+            .map_err(ExpanderError::WriteReadError)?;
+
+        *buffer = buf[0];
+
+        Ok(())
+    }
+
+    /// Writes one halfword to the given register
+    ///
+    /// Only use this function if you really have to. For most use cases, the crate provides simpler ways of interacting with the device.
+    ///
+    /// # Register pairs
+    /// Please see [`Register`] for more information about the register pairs and how they affect the half-word read and write functions.
+    async fn write_halfword(
+        &mut self,
+        register: Register,
+        data: u16,
+    ) -> Result<(), ExpanderError<E>> {
+        self.i2c
+            .write(
+                self.address,
+                &[register as u8, (data >> 8) as u8, data as u8],
+            )
+            .await
+            .map_err(ExpanderError::WriteError)
+    }
+
+    /// Reads one halfword of the given register
+    ///
+    /// Only use this function if you really have to. For most use cases, the crate provides simpler ways of interacting with the device.
+    ///
+    /// # Register pairs
+    /// Please see [`Register`] for more information about the register pairs and how they affect the half-word read and write functions.
+    async fn read_halfword(
+        &mut self,
+        register: Register,
+        buffer: &mut u16,
+    ) -> Result<(), ExpanderError<E>> {
+        let mut reg_val: [u8; 2] = [0x00; 2];
+
+        self.i2c
+            .write_read(self.address, &[register as u8], &mut reg_val)
+            .await
+            .map_err(ExpanderError::WriteReadError)?;
+
+        *buffer = (reg_val[0] as u16) << 8 | reg_val[1] as u16;
+
+        Ok(())
+    }
+}
+
+impl<I2C, E, C> AsyncStandardExpanderInterface<I2C, E> for Pca9535ImmediateAsync<I2C, C>
+where
+    E: Debug,
+    I2C: I2c<Error = E>,
+    C: Chip,
+{
+}
@@ -0,0 +1,39 @@
+//! Contains the async Expander interfaces and traits built on [`hal_async`].
+//!
+//! This module is only available if the "async" feature of this crate is enabled.
+use hal_async::i2c::{ErrorType, I2c};
+
+use super::{ExpanderError, Register};
+
+pub mod cached;
+pub mod immediate;
+pub mod standard;
+
+/// Async counterpart of the [`crate::Expander`] trait for IO expanders using an async I2C bus.
+#[allow(async_fn_in_trait)]
+pub trait AsyncExpander<I2C>
+where
+    I2C: I2c,
+{
+    async fn write_byte(
+        &mut self,
+        register: Register,
+        data: u8,
+    ) -> Result<(), ExpanderError<<I2C as ErrorType>::Error>>;
+
+    async fn read_byte(
+        &mut self,
+        register: Register,
+        buffer: &mut u8,
+    ) -> Result<(), ExpanderError<<I2C as ErrorType>::Error>>;
+    async fn write_halfword(
+        &mut self,
+        register: Register,
+        data: u16,
+    ) -> Result<(), ExpanderError<<I2C as ErrorType>::Error>>;
+    async fn read_halfword(
+        &mut self,
+        register: Register,
+        buffer: &mut u16,
+    ) -> Result<(), ExpanderError<<I2C as ErrorType>::Error>>;
+}
@@ -0,0 +1,368 @@
+//! Implements the async standard interface for all types implementing the [`AsyncExpander`] trait.
+use core::fmt::Debug;
+
+use hal_async::i2c::I2c;
+
+use super::{AsyncExpander, ExpanderError, Register};
+use crate::expander::check_pin;
+use crate::GPIOBank;
+
+/// Async counterpart of the [`crate::StandardExpanderInterface`].
+///
+/// This interface does not track the state of the pins! Therefore, the user needs to ensure the pins are in input or output configuration before
+/// proceeding to call functions related to input or output pins. Otherwise, the results of those functions might not cause the expected behavior of the device.
+#[allow(async_fn_in_trait)]
+pub trait AsyncStandardExpanderInterface<I2C, E>: AsyncExpander<I2C>
+where
+    E: Debug,
+    I2C: I2c<Error = E>,
+{
+    /// Drives given pin high.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
+    async fn pin_set_high(&mut self, bank: GPIOBank, pin: u8) -> Result<(), ExpanderError<E>> {
+        check_pin(pin)?;
+
+        let register = match bank {
+            GPIOBank::Bank0 => Register::OutputPort0,
+            GPIOBank::Bank1 => Register::OutputPort1,
+        };
+
+        let mut reg_val: u8 = 0x00;
+
+        self.read_byte(register, &mut reg_val).await?;
+
+        self.write_byte(register, reg_val | (0x01 << pin)).await
+    }
+
+    /// Drives given pin low.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
+    async fn pin_set_low(&mut self, bank: GPIOBank, pin: u8) -> Result<(), ExpanderError<E>> {
+        check_pin(pin)?;
+
+        let register = match bank {
+            GPIOBank::Bank0 => Register::OutputPort0,
+            GPIOBank::Bank1 => Register::OutputPort1,
+        };
+
+        let mut reg_val: u8 = 0x00;
+
+        self.read_byte(register, &mut reg_val).await?;
+
+        self.write_byte(register, reg_val & !(0x01 << pin)).await
+    }
+
+    /// Toggles the output state of the given pin.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
+    async fn pin_toggle(&mut self, bank: GPIOBank, pin: u8) -> Result<(), ExpanderError<E>> {
+        check_pin(pin)?;
+
+        let register = match bank {
+            GPIOBank::Bank0 => Register::OutputPort0,
+            GPIOBank::Bank1 => Register::OutputPort1,
+        };
+
+        let mut reg_val: u8 = 0x00;
+
+        self.read_byte(register, &mut reg_val).await?;
+
+        self.write_byte(register, reg_val ^ (0x01 << pin)).await
+    }
+
+    /// Checks if the given pin is set to drive `high`.
+    ///
+    /// Unlike [`AsyncStandardExpanderInterface::pin_is_high()`], this function checks the value inside the output register of the device and not the actual input state of the pin.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
+    async fn pin_is_set_high(&mut self, bank: GPIOBank, pin: u8) -> Result<bool, ExpanderError<E>> {
+        check_pin(pin)?;
+
+        let register = match bank {
+            GPIOBank::Bank0 => Register::OutputPort0,
+            GPIOBank::Bank1 => Register::OutputPort1,
+        };
+
+        let mut reg_val: u8 = 0x00;
+
+        self.read_byte(register, &mut reg_val).await?;
+
+        match (reg_val >> pin) & 1 {
+            1 => Ok(true),
+            _ => Ok(false),
+        }
+    }
+
+    /// Checks if the given pin is set to drive `low`.
+    ///
+    /// Unlike [`AsyncStandardExpanderInterface::pin_is_low()`], this function checks the value inside the output register of the device and not the actual input state of the pin.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
+    async fn pin_is_set_low(&mut self, bank: GPIOBank, pin: u8) -> Result<bool, ExpanderError<E>> {
+        Ok(!self.pin_is_set_high(bank, pin).await?)
+    }
+
+    /// Checks if the input state of the given pin is `high`. This function works with pins configured as inputs as well as outputs.
+    ///
+    /// The function result does not necessarily represent the logic level of the applied voltage at the given pin but the value inside the input register of the device.
+    /// Which is `1` or `0` Depending on the current polarity inversion configuration of the pin.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
+    async fn pin_is_high(&mut self, bank: GPIOBank, pin: u8) -> Result<bool, ExpanderError<E>> {
+        check_pin(pin)?;
+
+        let register = match bank {
+            GPIOBank::Bank0 => Register::InputPort0,
+            GPIOBank::Bank1 => Register::InputPort1,
+        };
+
+        let mut reg_val: u8 = 0x00;
+
+        self.read_byte(register, &mut reg_val).await?;
+
+        match (reg_val >> pin) & 1 {
+            1 => Ok(true),
+            _ => Ok(false),
+        }
+    }
+
+    /// Checks if the input state of the given pin is `low`. This function works with pins configured as inputs as well as outputs.
+    ///
+    /// The function result does not necessarily represent the logic level of the applied voltage at the given pin but the value inside the input register of the device.
+    /// Which is `1` or `0` Depending on the current polarity inversion configuration of the pin.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
+    async fn pin_is_low(&mut self, bank: GPIOBank, pin: u8) -> Result<bool, ExpanderError<E>> {
+        Ok(!self.pin_is_high(bank, pin).await?)
+    }
+
+    /// Configures given pin as input.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
+    async fn pin_into_input(&mut self, bank: GPIOBank, pin: u8) -> Result<(), ExpanderError<E>> {
+        check_pin(pin)?;
+
+        let register = match bank {
+            GPIOBank::Bank0 => Register::ConfigurationPort0,
+            GPIOBank::Bank1 => Register::ConfigurationPort1,
+        };
+
+        let mut reg_val: u8 = 0x00;
+
+        self.read_byte(register, &mut reg_val).await?;
+
+        self.write_byte(register, reg_val | (0x01 << pin)).await
+    }
+
+    /// Configures the given pin as output.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
+    async fn pin_into_output(&mut self, bank: GPIOBank, pin: u8) -> Result<(), ExpanderError<E>> {
+        check_pin(pin)?;
+
+        let register = match bank {
+            GPIOBank::Bank0 => Register::ConfigurationPort0,
+            GPIOBank::Bank1 => Register::ConfigurationPort1,
+        };
+
+        let mut reg_val: u8 = 0x00;
+
+        self.read_byte(register, &mut reg_val).await?;
+
+        self.write_byte(register, reg_val & !(0x01 << pin)).await
+    }
+
+    /// Sets the input polarity of the given pin to inverted.
+    ///
+    /// A logic high voltage applied at this input pin results in a `0` written to the devices input register and thus being registered as `low` by the driver.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
+    async fn pin_inverse_polarity(
+        &mut self,
+        bank: GPIOBank,
+        pin: u8,
+    ) -> Result<(), ExpanderError<E>> {
+        check_pin(pin)?;
+
+        let register = match bank {
+            GPIOBank::Bank0 => Register::PolarityInversionPort0,
+            GPIOBank::Bank1 => Register::PolarityInversionPort1,
+        };
+
+        let mut reg_val: u8 = 0x00;
+
+        self.read_byte(register, &mut reg_val).await?;
+
+        self.write_byte(register, reg_val | (0x01 << pin)).await
+    }
+
+    /// Sets the input polarity of the given pin to normal.
+    ///
+    /// A logic high voltage applied at an input pin results in a `1` written to the device's input register, thus being registered as `high` by the driver.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
+    async fn pin_normal_polarity(
+        &mut self,
+        bank: GPIOBank,
+        pin: u8,
+    ) -> Result<(), ExpanderError<E>> {
+        check_pin(pin)?;
+
+        let register = match bank {
+            GPIOBank::Bank0 => Register::PolarityInversionPort0,
+            GPIOBank::Bank1 => Register::PolarityInversionPort1,
+        };
+
+        let mut reg_val: u8 = 0x00;
+
+        self.read_byte(register, &mut reg_val).await?;
+
+        self.write_byte(register, reg_val & !(0x01 << pin)).await
+    }
+
+    /// Drives all pins of the given bank according to the given value, where bit `n` holds the state of pin `n`.
+    async fn port_write(&mut self, bank: GPIOBank, value: u8) -> Result<(), ExpanderError<E>> {
+        let register = match bank {
+            GPIOBank::Bank0 => Register::OutputPort0,
+            GPIOBank::Bank1 => Register::OutputPort1,
+        };
+
+        self.write_byte(register, value).await
+    }
+
+    /// Reads the input states of all pins of the given bank, where bit `n` holds the state of pin `n`.
+    ///
+    /// Like [`AsyncStandardExpanderInterface::pin_is_high()`], the result represents the value inside the input register of the device.
+    async fn port_read(&mut self, bank: GPIOBank) -> Result<u8, ExpanderError<E>> {
+        let register = match bank {
+            GPIOBank::Bank0 => Register::InputPort0,
+            GPIOBank::Bank1 => Register::InputPort1,
+        };
+
+        let mut reg_val: u8 = 0x00;
+
+        self.read_byte(register, &mut reg_val).await?;
+
+        Ok(reg_val)
+    }
+
+    /// Drives all pins of both banks according to the given value using a single register pair write.
+    ///
+    /// # Pin masks
+    /// Bit `n` of the value represents pin `n` of [`GPIOBank::Bank0`] and bit `8 + n` represents pin `n` of [`GPIOBank::Bank1`].
+    async fn write_outputs(&mut self, value: u16) -> Result<(), ExpanderError<E>> {
+        self.write_halfword(Register::OutputPort0, value.swap_bytes())
+            .await
+    }
+
+    /// Reads the input states of all pins of both banks using a single register pair read.
+    ///
+    /// # Pin masks
+    /// Bit `n` of the result represents pin `n` of [`GPIOBank::Bank0`] and bit `8 + n` represents pin `n` of [`GPIOBank::Bank1`].
+    async fn read_inputs(&mut self) -> Result<u16, ExpanderError<E>> {
+        let mut reg_val: u16 = 0x0000;
+
+        self.read_halfword(Register::InputPort0, &mut reg_val)
+            .await?;
+
+        Ok(reg_val.swap_bytes())
+    }
+
+    /// Drives all pins set in the given mask high, leaving the other pins untouched.
+    ///
+    /// # Pin masks
+    /// Bit `n` of the mask represents pin `n` of [`GPIOBank::Bank0`] and bit `8 + n` represents pin `n` of [`GPIOBank::Bank1`].
+    async fn pins_set_high(&mut self, mask: u16) -> Result<(), ExpanderError<E>> {
+        let mut reg_val: u16 = 0x0000;
+
+        self.read_halfword(Register::OutputPort0, &mut reg_val)
+            .await?;
+
+        self.write_halfword(Register::OutputPort0, reg_val | mask.swap_bytes())
+            .await
+    }
+
+    /// Drives all pins set in the given mask low, leaving the other pins untouched.
+    ///
+    /// # Pin masks
+    /// Bit `n` of the mask represents pin `n` of [`GPIOBank::Bank0`] and bit `8 + n` represents pin `n` of [`GPIOBank::Bank1`].
+    async fn pins_set_low(&mut self, mask: u16) -> Result<(), ExpanderError<E>> {
+        let mut reg_val: u16 = 0x0000;
+
+        self.read_halfword(Register::OutputPort0, &mut reg_val)
+            .await?;
+
+        self.write_halfword(Register::OutputPort0, reg_val & !mask.swap_bytes())
+            .await
+    }
+
+    /// Toggles the output state of all pins set in the given mask, leaving the other pins untouched.
+    ///
+    /// # Pin masks
+    /// Bit `n` of the mask represents pin `n` of [`GPIOBank::Bank0`] and bit `8 + n` represents pin `n` of [`GPIOBank::Bank1`].
+    async fn pins_toggle(&mut self, mask: u16) -> Result<(), ExpanderError<E>> {
+        let mut reg_val: u16 = 0x0000;
+
+        self.read_halfword(Register::OutputPort0, &mut reg_val)
+            .await?;
+
+        self.write_halfword(Register::OutputPort0, reg_val ^ mask.swap_bytes())
+            .await
+    }
+
+    /// Configures all pins set in the given mask as inputs, leaving the other pins untouched.
+    ///
+    /// # Pin masks
+    /// Bit `n` of the mask represents pin `n` of [`GPIOBank::Bank0`] and bit `8 + n` represents pin `n` of [`GPIOBank::Bank1`].
+    async fn pins_into_input(&mut self, mask: u16) -> Result<(), ExpanderError<E>> {
+        let mut reg_val: u16 = 0x0000;
+
+        self.read_halfword(Register::ConfigurationPort0, &mut reg_val)
+            .await?;
+
+        self.write_halfword(Register::ConfigurationPort0, reg_val | mask.swap_bytes())
+            .await
+    }
+
+    /// Configures all pins set in the given mask as outputs, leaving the other pins untouched.
+    ///
+    /// # Pin masks
+    /// Bit `n` of the mask represents pin `n` of [`GPIOBank::Bank0`] and bit `8 + n` represents pin `n` of [`GPIOBank::Bank1`].
+    async fn pins_into_output(&mut self, mask: u16) -> Result<(), ExpanderError<E>> {
+        let mut reg_val: u16 = 0x0000;
+
+        self.read_halfword(Register::ConfigurationPort0, &mut reg_val)
+            .await?;
+
+        self.write_halfword(Register::ConfigurationPort0, reg_val & !mask.swap_bytes())
+            .await
+    }
+
+    /// Sets the input polarity of all pins to inverted.
+    ///
+    /// A logic high voltage applied at an input pin results in a `0` written to the device's input register, thus being registered as `low` by the driver.
+    async fn inverse_polarity(&mut self) -> Result<(), ExpanderError<E>> {
+        self.write_halfword(Register::PolarityInversionPort0, 0xFFFF_u16)
+            .await
+    }
+
+    /// Sets the input polarity of all pins to normal.
+    ///
+    /// A logic high voltage applied at an input pin results in a `1` written to the device's input register, thus being registered as `high` by the driver.
+    async fn normal_polarity(&mut self) -> Result<(), ExpanderError<E>> {
+        self.write_halfword(Register::PolarityInversionPort0, 0x0_u16)
+            .await
+    }
+}
@@ -1,22 +1,128 @@
 //! Contains the implementation of the Cached Expander interface.
 use core::fmt::Debug;
 
-use hal::digital::InputPin;
+use hal::delay::DelayNs;
+use hal::digital::{Error as _, InputPin, OutputPin};
 use hal::i2c::I2c;
 
-use crate::StandardExpanderInterface;
+use crate::chip::{Chip, Pca9535};
+use crate::{ExpanderPins, StandardExpanderInterface};
+
+use super::io::SplitPins;
+use super::retry::{NoDelay, RetryPolicy};
+use super::{
+    check_address, pulse_reset_pin, Expander, ExpanderError, NoResetPin, Register, SyncExpander,
+};
+
+/// Input changes detected by [`Pca9535Cached::input_changes()`].
+///
+/// Each mask holds one bit per pin, where bit `n` represents pin `n` of [`crate::GPIOBank::Bank0`] and bit `8 + n` represents pin `n` of [`crate::GPIOBank::Bank1`].
+/// The masks refer to the values of the input registers, so a pin with inverted polarity reports a `rising` edge once the voltage applied to it goes low.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct InputChanges {
+    /// Pins whose input register value changed
+    pub changed: u16,
+    /// Pins whose input register value changed from `0` to `1`
+    pub rising: u16,
+    /// Pins whose input register value changed from `1` to `0`
+    pub falling: u16,
+}
+
+impl InputChanges {
+    /// Returns `true` if no pin changed.
+    pub fn is_empty(&self) -> bool {
+        self.changed == 0
+    }
+}
+
+/// Registers whose cached value differs from the value read from the device, as reported by [`Pca9535Cached::verify_cache()`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct CacheMismatches {
+    mismatched: u8,
+    cached: [u8; 8],
+    actual: [u8; 8],
+}
+
+impl CacheMismatches {
+    /// Returns `true` if all cached values match the device.
+    pub fn is_empty(&self) -> bool {
+        self.mismatched == 0
+    }
+
+    /// Returns `true` if the cached value of the given register differs from the device.
+    pub fn contains(&self, register: Register) -> bool {
+        self.mismatched & (0x01 << register as u8) != 0
+    }
+
+    /// Returns the cached and the actual value of the given register if they differ.
+    pub fn get(&self, register: Register) -> Option<(u8, u8)> {
+        self.contains(register).then(|| {
+            (
+                self.cached[register as usize],
+                self.actual[register as usize],
+            )
+        })
+    }
 
-use super::{Expander, ExpanderError, Register};
+    /// Iterates over all mismatching registers, yielding the register as well as its cached and actual value.
+    pub fn iter(&self) -> impl Iterator<Item = (Register, u8, u8)> + '_ {
+        Register::ALL.into_iter().filter_map(|register| {
+            self.get(register)
+                .map(|(cached, actual)| (register, cached, actual))
+        })
+    }
+
+    /// Returns the first mismatch of the output, polarity inversion or configuration registers as [`ExpanderError::CacheMismatch`].
+    fn first_error<E: Debug>(&self) -> Option<ExpanderError<E>> {
+        self.iter()
+            .find(|(register, _, _)| !register.is_input())
+            .map(|(register, cached, actual)| ExpanderError::CacheMismatch {
+                register,
+                cached,
+                actual,
+            })
+    }
+}
+
+/// Policy of [`Pca9535Cached`] to periodically verify its cache against the device.
+///
+/// The operations are counted on each register access of the [`Expander`] trait, which all functions of the [`StandardExpanderInterface`] and the hal pins are based on.
+/// Once the given number of operations is reached, [`Pca9535Cached::verify_cache()`] is run before the next operation. Only mismatches of the output, polarity inversion
+/// and configuration registers are considered, as the input registers legitimately change with the voltage levels applied to the pins.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum VerifyPolicy {
+    /// The cache is only verified by calling [`Pca9535Cached::verify_cache()`]
+    #[default]
+    Never,
+    /// Verifies the cache every given number of operations. A mismatch resyncs the cache and fails the operation with [`ExpanderError::CacheMismatch`]
+    Report(u32),
+    /// Verifies the cache every given number of operations. A mismatch resyncs the cache and the operation continues
+    Resync(u32),
+}
+
+/// Policy of [`Pca9535Cached`] on when written register values are transferred to the device.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum WritePolicy {
+    /// Each write is transferred to the device immediately
+    #[default]
+    WriteThrough,
+    /// Writes to the output and configuration registers only update the cache and mark the registers dirty, until [`Pca9535Cached::flush()`] transfers them to the device.
+    /// Writes to the polarity inversion registers are still transferred immediately, as they affect the cached input registers.
+    WriteBack,
+}
 
 #[derive(Debug)]
-pub struct Pca9535Cached<I2C, IP>
+pub struct Pca9535Cached<I2C, IP, C = Pca9535, RP = NoResetPin, RD = NoDelay>
 where
     I2C: I2c,
     IP: InputPin,
+    C: Chip,
 {
     address: u8,
     i2c: I2C,
     interrupt_pin: IP,
+    chip: C,
+    reset_pin: RP,
 
     input_port_0: u8,
     input_port_1: u8,
@@ -26,6 +132,14 @@ where
     polarity_inversion_port_1: u8,
     configuration_port_0: u8,
     configuration_port_1: u8,
+
+    verify_policy: VerifyPolicy,
+    operations: u32,
+    write_policy: WritePolicy,
+    dirty: u8,
+    retry_policy: RetryPolicy<RD>,
+    write_verification: bool,
+    unreported_inputs: Option<u16>,
 }
 
 impl<I2C, E, IP> Pca9535Cached<I2C, IP>
@@ -43,28 +157,67 @@ where
     ///
     /// If the device was used before calling this function and should keep its state, you should set init_defaults to `false`. This triggers a bus transaction to read out all the devices' registers and caches the received values.
     ///
-    /// # Panics
-    /// If the given device hardware address is outside the permittable range of `32-39`.
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidAddress`] if the given device hardware address is outside the permittable range of `32-39`.
     pub fn new(
         i2c: I2C,
         address: u8,
         interrupt_pin: IP,
         init_defaults: bool,
     ) -> Result<Self, ExpanderError<E>> {
-        assert!(address > 31 && address < 40);
+        Self::with_chip(i2c, address, interrupt_pin, init_defaults, Pca9535)
+    }
+}
+
+impl<I2C, E, IP, C> Pca9535Cached<I2C, IP, C>
+where
+    IP: InputPin,
+    E: Debug,
+    I2C: I2c<Error = E>,
+    C: Chip,
+{
+    /// Creates a new cached instance of the given chip variant.
+    ///
+    /// If `init_defaults` is set to `true`, the cache is initialized using the power-on defaults of the chip variant (see [`Chip::power_on_default()`]).
+    /// Please see [`Pca9535Cached::new()`] for more information on the cache initialization.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidAddress`] if the given device hardware address is outside the permittable range of the chip variant.
+    ///
+    /// # Compile errors
+    /// The chip variant needs to be a 16-bit expander (see [`Chip::BANKS`]).
+    pub fn with_chip(
+        i2c: I2C,
+        address: u8,
+        interrupt_pin: IP,
+        init_defaults: bool,
+        chip: C,
+    ) -> Result<Self, ExpanderError<E>> {
+        const { assert!(C::BANKS == 2, "the chip variant is not a 16-bit expander") };
+
+        check_address::<C, _>(address)?;
 
         let mut expander = Self {
             address,
             i2c,
             interrupt_pin,
-            input_port_0: 0x00,
-            input_port_1: 0x00,
-            output_port_0: 0xFF,
-            output_port_1: 0xFF,
-            polarity_inversion_port_0: 0x00,
-            polarity_inversion_port_1: 0x00,
-            configuration_port_0: 0xFF,
-            configuration_port_1: 0xFF,
+            chip,
+            reset_pin: NoResetPin,
+            input_port_0: C::power_on_default(Register::InputPort0),
+            input_port_1: C::power_on_default(Register::InputPort1),
+            output_port_0: C::power_on_default(Register::OutputPort0),
+            output_port_1: C::power_on_default(Register::OutputPort1),
+            polarity_inversion_port_0: C::power_on_default(Register::PolarityInversionPort0),
+            polarity_inversion_port_1: C::power_on_default(Register::PolarityInversionPort1),
+            configuration_port_0: C::power_on_default(Register::ConfigurationPort0),
+            configuration_port_1: C::power_on_default(Register::ConfigurationPort1),
+            verify_policy: VerifyPolicy::Never,
+            operations: 0,
+            write_policy: WritePolicy::WriteThrough,
+            dirty: 0x00,
+            retry_policy: RetryPolicy::default(),
+            write_verification: false,
+            unreported_inputs: None,
         };
 
         if !init_defaults {
@@ -73,55 +226,442 @@ where
 
         Ok(expander)
     }
+}
+
+impl<I2C, IP, C, RP, RD> Pca9535Cached<I2C, IP, C, RP, RD>
+where
+    I2C: I2c,
+    IP: InputPin,
+    C: Chip,
+{
+    /// Rebuilds the expander with the reset pin and retry policy returned by the given closure, which receives the current ones and may return an
+    /// additional value.
+    fn map_parts<RP2, RD2, T>(
+        self,
+        f: impl FnOnce(RP, RetryPolicy<RD>) -> (RP2, RetryPolicy<RD2>, T),
+    ) -> (Pca9535Cached<I2C, IP, C, RP2, RD2>, T) {
+        let (reset_pin, retry_policy, value) = f(self.reset_pin, self.retry_policy);
+
+        (
+            Pca9535Cached {
+                address: self.address,
+                i2c: self.i2c,
+                interrupt_pin: self.interrupt_pin,
+                chip: self.chip,
+                reset_pin,
+                input_port_0: self.input_port_0,
+                input_port_1: self.input_port_1,
+                output_port_0: self.output_port_0,
+                output_port_1: self.output_port_1,
+                polarity_inversion_port_0: self.polarity_inversion_port_0,
+                polarity_inversion_port_1: self.polarity_inversion_port_1,
+                configuration_port_0: self.configuration_port_0,
+                configuration_port_1: self.configuration_port_1,
+                verify_policy: self.verify_policy,
+                operations: self.operations,
+                write_policy: self.write_policy,
+                dirty: self.dirty,
+                retry_policy,
+                write_verification: self.write_verification,
+                unreported_inputs: self.unreported_inputs,
+            },
+            value,
+        )
+    }
+}
+
+impl<I2C, E, IP, C, RD> Pca9535Cached<I2C, IP, C, NoResetPin, RD>
+where
+    IP: InputPin,
+    E: Debug,
+    I2C: I2c<Error = E>,
+    C: Chip,
+{
+    /// Hands over the pin connected to the active-low RESET input of the device, which enables [`Pca9535Cached::reset()`].
+    ///
+    /// The pin is driven `high` right away to release the device from reset.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::ResetPinError`] if setting the state of the reset pin failed.
+    ///
+    /// # Compile errors
+    /// The chip variant needs to provide a RESET input (see [`Chip::HAS_RESET_PIN`]).
+    pub fn with_reset_pin<RP>(
+        self,
+        mut reset_pin: RP,
+    ) -> Result<Pca9535Cached<I2C, IP, C, RP, RD>, ExpanderError<E>>
+    where
+        RP: OutputPin,
+    {
+        const { assert!(C::HAS_RESET_PIN, "the chip variant has no RESET input") };
+
+        reset_pin
+            .set_high()
+            .map_err(|err| ExpanderError::ResetPinError(err.kind()))?;
+
+        Ok(self
+            .map_parts(|_, retry_policy| (reset_pin, retry_policy, ()))
+            .0)
+    }
+}
+
+impl<I2C, E, IP, C, RP, RD> Pca9535Cached<I2C, IP, C, RP, RD>
+where
+    IP: InputPin,
+    E: Debug,
+    I2C: I2c<Error = E>,
+    C: Chip,
+    RD: DelayNs,
+{
+    /// Returns the chip variant of the expander.
+    pub fn chip(&self) -> C {
+        self.chip
+    }
 
     /// Destroys the expander struct, returning the contained I2C and interrupt pin
+    ///
+    /// Use [`Pca9535Cached::release_reset_pin()`] beforehand to retrieve the reset pin.
     pub fn destroy(self) -> (I2C, IP) {
         (self.i2c, self.interrupt_pin)
     }
 
+    /// Returns the reset pin handed over using [`Pca9535Cached::with_reset_pin()`] together with the expander, which keeps its cached state.
+    pub fn release_reset_pin(self) -> (Pca9535Cached<I2C, IP, C, NoResetPin, RD>, RP) {
+        self.map_parts(|reset_pin, retry_policy| (NoResetPin, retry_policy, reset_pin))
+    }
+
+    /// Sets the policy on retrying failed bus transactions, see [`RetryPolicy`].
+    pub fn with_retry_policy<RD2>(
+        self,
+        retry_policy: RetryPolicy<RD2>,
+    ) -> Pca9535Cached<I2C, IP, C, RP, RD2>
+    where
+        RD2: DelayNs,
+    {
+        self.map_parts(|reset_pin, _| (reset_pin, retry_policy, ()))
+            .0
+    }
+
+    /// Enables or disables the verification of writes to the output and configuration registers.
+    ///
+    /// If enabled, each output or configuration register written to the device is read back in a separate bus transaction. This includes the writes of
+    /// [`Pca9535Cached::flush()`] and [`Pca9535Cached::restore()`]. A value differing from the written one is reported as [`ExpanderError::WriteVerifyMismatch`],
+    /// in which case the cache takes over the values read from the device for all written registers.
+    pub fn set_write_verification(&mut self, enabled: bool) {
+        self.write_verification = enabled;
+    }
+
+    /// Re-applies the cached output, polarity inversion and configuration registers to the device.
+    ///
+    /// Use this function after the device lost its state unexpectedly, for example due to a brown-out. The output registers are written first,
+    /// so pins which are configured as outputs again immediately drive their last known state. Afterwards, the input registers are read to update the cache.
+    pub fn restore(&mut self) -> Result<(), ExpanderError<E>> {
+        for register in [
+            Register::OutputPort0,
+            Register::PolarityInversionPort0,
+            Register::ConfigurationPort0,
+        ] {
+            self.bus_write(
+                register,
+                &[
+                    self.get_cached(register),
+                    self.get_cached(register.get_neighbor()),
+                ],
+            )?;
+        }
+
+        self.dirty = 0x00;
+
+        self.read_inputs_into_cache()
+    }
+
+    /// Reads all eight device registers and reports which of them differ from their cached value.
+    ///
+    /// The cache of the output, polarity inversion and configuration registers is left untouched, use [`Pca9535Cached::resync()`] to refresh it.
+    /// Registers with pending writes of the [`WritePolicy::WriteBack`] policy are not reported.
+    /// As reading the input registers clears the interrupt of the device, the cached input registers are always updated. Input changes picked up this way are
+    /// kept and reported by the next call of [`Pca9535Cached::input_changes()`].
+    pub fn verify_cache(&mut self) -> Result<CacheMismatches, ExpanderError<E>> {
+        let mut mismatches = CacheMismatches::default();
+
+        self.unreported_inputs.get_or_insert(self.cached_inputs());
+
+        for register in [
+            Register::InputPort0,
+            Register::OutputPort0,
+            Register::PolarityInversionPort0,
+            Register::ConfigurationPort0,
+        ] {
+            let mut buf: [u8; 2] = [0x00; 2];
+
+            self.bus_write_read(register, &mut buf)?;
+
+            for (register, actual) in [(register, buf[0]), (register.get_neighbor(), buf[1])] {
+                let cached = self.get_cached(register);
+
+                mismatches.cached[register as usize] = cached;
+                mismatches.actual[register as usize] = actual;
+
+                if cached != actual && self.dirty & (0x01 << register as u8) == 0 {
+                    mismatches.mismatched |= 0x01 << register as u8;
+                }
+
+                if register.is_input() {
+                    self.set_cached(register, actual);
+                }
+            }
+        }
+
+        Ok(mismatches)
+    }
+
+    /// Refreshes the cache by reading out all the device registers.
+    ///
+    /// Pending writes of the [`WritePolicy::WriteBack`] policy are flushed beforehand.
+    pub fn resync(&mut self) -> Result<(), ExpanderError<E>> {
+        self.flush()?;
+
+        Self::init_cache(self)
+    }
+
+    /// Sets the policy on when written register values are transferred to the device.
+    ///
+    /// Pending writes are flushed when switching to [`WritePolicy::WriteThrough`].
+    pub fn set_write_policy(&mut self, policy: WritePolicy) -> Result<(), ExpanderError<E>> {
+        self.write_policy = policy;
+
+        if policy == WritePolicy::WriteThrough {
+            self.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if there are pending writes of the [`WritePolicy::WriteBack`] policy, which have not been flushed to the device yet.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty != 0x00
+    }
+
+    /// Transfers all pending writes of the [`WritePolicy::WriteBack`] policy to the device.
+    ///
+    /// Each register pair with pending writes is transferred in a single bus transaction, with the output registers being written before the configuration registers.
+    /// Thus, pins which are switched to outputs immediately drive their new state.
+    pub fn flush(&mut self) -> Result<(), ExpanderError<E>> {
+        for register in [Register::OutputPort0, Register::ConfigurationPort0] {
+            let neighbor = register.get_neighbor();
+            let mask = (0x01 << register as u8) | (0x01 << neighbor as u8);
+
+            match self.dirty & mask {
+                0x00 => continue,
+                dirty if dirty == mask => self.bus_write(
+                    register,
+                    &[self.get_cached(register), self.get_cached(neighbor)],
+                ),
+                dirty => {
+                    let register = if dirty & (0x01 << register as u8) != 0 {
+                        register
+                    } else {
+                        neighbor
+                    };
+
+                    self.bus_write(register, &[self.get_cached(register)])
+                }
+            }?;
+
+            self.dirty &= !mask;
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if a write to the given register is deferred by the [`WritePolicy::WriteBack`] policy, which marks the register dirty.
+    fn defer_write(&mut self, register: Register) -> bool {
+        let deferred =
+            self.write_policy == WritePolicy::WriteBack && !register.is_polarity_inversion();
+
+        if deferred {
+            self.dirty |= 0x01 << register as u8;
+        }
+
+        deferred
+    }
+
+    /// Sets the policy to periodically verify the cache against the device and restarts the operation count.
+    pub fn set_verify_policy(&mut self, policy: VerifyPolicy) {
+        self.verify_policy = policy;
+        self.operations = 0;
+    }
+
+    /// Counts an operation and verifies the cache once required by the [`VerifyPolicy`].
+    ///
+    /// On a mismatch the cache is refreshed from the device, except for registers with pending writes of the [`WritePolicy::WriteBack`] policy. As operations
+    /// include reads, pending writes are never flushed here.
+    fn count_operation(&mut self) -> Result<(), ExpanderError<E>> {
+        let (interval, report) = match self.verify_policy {
+            VerifyPolicy::Never => return Ok(()),
+            VerifyPolicy::Report(interval) => (interval, true),
+            VerifyPolicy::Resync(interval) => (interval, false),
+        };
+
+        self.operations += 1;
+
+        if self.operations < interval {
+            return Ok(());
+        }
+
+        self.operations = 0;
+
+        let mismatches = self.verify_cache()?;
+
+        match mismatches.first_error() {
+            Some(error) => {
+                Self::init_cache(self)?;
+
+                if report {
+                    Err(error)
+                } else {
+                    Ok(())
+                }
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Checks which input pins changed since the input registers were last read.
+    ///
+    /// If the interrupt pin is held at a `low` voltage level, both input registers are read in a single bus transaction, compared against their cached values
+    /// and the cache is updated. Otherwise, no bus traffic is created and an empty [`InputChanges`] is returned.
+    ///
+    /// The changes are determined relative to the cached input registers. Any change which has already been picked up by a previous read of an input register
+    /// (for example by [`crate::StandardExpanderInterface::pin_is_high()`]) is therefore not reported again. Changes picked up by
+    /// [`Pca9535Cached::verify_cache()`], which clears the interrupt, are reported even if the interrupt pin is not `low` anymore.
+    pub fn input_changes(&mut self) -> Result<InputChanges, ExpanderError<E>> {
+        let active = self.interrupt_active()?;
+        let previous = self.unreported_inputs.unwrap_or(self.cached_inputs());
+
+        if active {
+            self.read_inputs_into_cache()?;
+        }
+
+        self.unreported_inputs = None;
+
+        let current = self.cached_inputs();
+        let changed = previous ^ current;
+
+        Ok(InputChanges {
+            changed,
+            rising: changed & current,
+            falling: changed & previous,
+        })
+    }
+
     /// Initializes the device's cache by reading out all the required device registers.
+    ///
+    /// Registers with pending writes of the [`WritePolicy::WriteBack`] policy keep their cached value.
     fn init_cache(expander: &mut Self) -> Result<(), ExpanderError<E>> {
-        let mut buf: [u8; 2] = [0x00, 0x00];
-
-        expander
-            .i2c
-            .write_read(
-                expander.address,
-                &[Register::ConfigurationPort0 as u8],
-                &mut buf,
-            )
-            .map_err(ExpanderError::WriteReadError)?;
-        expander.configuration_port_0 = buf[0];
-        expander.configuration_port_1 = buf[1];
-
-        expander
-            .i2c
-            .write_read(expander.address, &[Register::InputPort0 as u8], &mut buf)
-            .map_err(ExpanderError::WriteReadError)?;
-        expander.input_port_0 = buf[0];
-        expander.input_port_1 = buf[1];
-
-        expander
-            .i2c
-            .write_read(expander.address, &[Register::OutputPort0 as u8], &mut buf)
-            .map_err(ExpanderError::WriteReadError)?;
-        expander.output_port_0 = buf[0];
-        expander.output_port_1 = buf[1];
-
-        expander
-            .i2c
-            .write_read(
-                expander.address,
-                &[Register::PolarityInversionPort0 as u8],
-                &mut buf,
-            )
-            .map_err(ExpanderError::WriteReadError)?;
-        expander.polarity_inversion_port_0 = buf[0];
-        expander.polarity_inversion_port_1 = buf[1];
+        for register in [
+            Register::ConfigurationPort0,
+            Register::InputPort0,
+            Register::OutputPort0,
+            Register::PolarityInversionPort0,
+        ] {
+            let mut buf: [u8; 2] = [0x00, 0x00];
+
+            expander.bus_write_read(register, &mut buf)?;
+
+            for (register, value) in [(register, buf[0]), (register.get_neighbor(), buf[1])] {
+                if expander.dirty & (0x01 << register as u8) == 0 {
+                    expander.set_cached(register, value);
+                }
+            }
+        }
 
         Ok(())
     }
 
+    /// Returns both cached input registers, bit `n` representing pin `n` of [`GPIOBank::Bank0`] and bit `8 + n` pin `n` of [`GPIOBank::Bank1`].
+    fn cached_inputs(&self) -> u16 {
+        (self.get_cached(Register::InputPort1) as u16) << 8
+            | self.get_cached(Register::InputPort0) as u16
+    }
+
+    /// Reads both input registers in a single bus transaction and updates the cache.
+    fn read_inputs_into_cache(&mut self) -> Result<(), ExpanderError<E>> {
+        let mut buf: [u8; 2] = [0x00; 2];
+
+        self.bus_write_read(Register::InputPort0, &mut buf)?;
+
+        self.set_cached(Register::InputPort0, buf[0]);
+        self.set_cached(Register::InputPort1, buf[1]);
+
+        Ok(())
+    }
+
+    /// Writes the given data to the register and its neighbor in a single bus transaction, retrying as configured by the [`RetryPolicy`].
+    /// The written data is read back if write verification is enabled for the register, in which case the read back values of all written registers are cached.
+    fn bus_write(&mut self, register: Register, data: &[u8]) -> Result<(), ExpanderError<E>> {
+        let address = self.address;
+        let mut buf: [u8; 3] = [register as u8, 0x00, 0x00];
+        buf[1..=data.len()].copy_from_slice(data);
+        let bytes = &buf[..=data.len()];
+
+        self.retry_policy
+            .run(&mut self.i2c, |i2c| i2c.write(address, bytes))
+            .map_err(ExpanderError::WriteError)?;
+
+        if !self.write_verification || !register.is_output_or_configuration() {
+            return Ok(());
+        }
+
+        let mut buf: [u8; 2] = [0x00; 2];
+        let actual = &mut buf[..data.len()];
+
+        self.bus_write_read(register, actual)?;
+
+        let mut mismatch = None;
+
+        for ((register, expected), actual) in [register, register.get_neighbor()]
+            .into_iter()
+            .zip(data)
+            .zip(actual.iter())
+        {
+            self.set_cached(register, *actual);
+            self.dirty &= !(0x01 << register as u8);
+
+            if expected != actual {
+                mismatch.get_or_insert(ExpanderError::WriteVerifyMismatch {
+                    register,
+                    expected: *expected,
+                    actual: *actual,
+                });
+            }
+        }
+
+        mismatch.map_or(Ok(()), Err)
+    }
+
+    /// Reads the given register and its successors into the buffer in a single bus transaction, retrying as configured by the [`RetryPolicy`].
+    fn bus_write_read(
+        &mut self,
+        register: Register,
+        buffer: &mut [u8],
+    ) -> Result<(), ExpanderError<E>> {
+        let address = self.address;
+
+        self.retry_policy
+            .run(&mut self.i2c, |i2c| {
+                i2c.write_read(address, &[register as u8], buffer)
+            })
+            .map_err(ExpanderError::WriteReadError)
+    }
+
+    /// Returns `true` if the interrupt pin is held `low`, indicating a change of the device's input registers.
+    fn interrupt_active(&mut self) -> Result<bool, ExpanderError<E>> {
+        self.interrupt_pin
+            .is_low()
+            .map_err(|err| ExpanderError::InterruptPinError(err.kind()))
+    }
+
     fn get_cached(&self, register: Register) -> u8 {
         match register {
             Register::InputPort0 => self.input_port_0,
@@ -149,11 +689,45 @@ where
     }
 }
 
-impl<I2C, IP, E> Expander<I2C> for Pca9535Cached<I2C, IP>
+impl<I2C, E, IP, C, RP, RD> Pca9535Cached<I2C, IP, C, RP, RD>
+where
+    IP: InputPin,
+    E: Debug,
+    I2C: I2c<Error = E>,
+    C: Chip,
+    RP: OutputPin,
+    RD: DelayNs,
+{
+    /// Resets the device by pulsing its RESET input and restores the cache to the power-on defaults of the chip variant.
+    ///
+    /// As the input registers reflect the voltage levels applied to the pins, they are read from the device after the reset.
+    pub fn reset<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), ExpanderError<E>> {
+        pulse_reset_pin(&mut self.reset_pin, delay)?;
+
+        for register in [
+            Register::OutputPort0,
+            Register::OutputPort1,
+            Register::PolarityInversionPort0,
+            Register::PolarityInversionPort1,
+            Register::ConfigurationPort0,
+            Register::ConfigurationPort1,
+        ] {
+            self.set_cached(register, C::power_on_default(register));
+        }
+
+        self.dirty = 0x00;
+
+        self.read_inputs_into_cache()
+    }
+}
+
+impl<I2C, IP, E, C, RP, RD> Expander<I2C> for Pca9535Cached<I2C, IP, C, RP, RD>
 where
     IP: InputPin,
     I2C: I2c<Error = E>,
     E: Debug,
+    C: Chip,
+    RD: DelayNs,
 {
     /// Writes one byte to the given register
     ///
@@ -161,10 +735,16 @@ where
     ///
     /// # Cached
     /// If the bus write succeeds, the written data is cached to avoid the need for bus traffic upon reading the written register.
+    /// Using the [`WritePolicy::WriteBack`] policy, writes to the output and configuration registers only update the cache until [`Pca9535Cached::flush()`] is called.
     fn write_byte(&mut self, register: Register, data: u8) -> Result<(), ExpanderError<E>> {
-        self.i2c
-            .write(self.address, &[register as u8, data])
-            .map_err(ExpanderError::WriteError)?;
+        self.count_operation()?;
+
+        if self.defer_write(register) {
+            self.set_cached(register, data);
+            return Ok(());
+        }
+
+        self.bus_write(register, &[data])?;
 
         // As the IO Expander does not trigger an interrupt once the polarity inversion register value changes, writes to the polarity inversion registers need a special implementation in order to ensure that the input register cache stays up to date.
         if register.is_polarity_inversion() {
@@ -193,22 +773,17 @@ where
     ///
     /// # Cached
     /// This function only creates bus traffic if the provided interrupt pin is held at a `low` voltage level at the time of the function call and the provided register is an input register.
-    /// In that case, the data is being read from the device, as the device's interrupt output indicates a data change. Otherwise the cached value is returned without causing any bus traffic.
+    /// In that case, both input registers are read from the device in a single bus transaction, as the device's interrupt output indicates a data change.
+    /// Otherwise the cached value is returned without causing any bus traffic.
     fn read_byte(&mut self, register: Register, buffer: &mut u8) -> Result<(), ExpanderError<E>> {
-        if self.interrupt_pin.is_low().unwrap() && register.is_input() {
-            let mut buf = [0u8];
+        self.count_operation()?;
 
-            self.i2c
-                .write_read(self.address, &[register as u8], &mut buf)
-                .map_err(ExpanderError::WriteReadError)?;
-
-            self.set_cached(register, buf[0]);
-
-            *buffer = buf[0];
-        } else {
-            *buffer = self.get_cached(register);
+        if register.is_input() && self.interrupt_active()? {
+            self.read_inputs_into_cache()?;
         }
 
+        *buffer = self.get_cached(register);
+
         Ok(())
     }
 
@@ -221,13 +796,18 @@ where
     ///
     /// # Cached
     /// If the bus write succeeds, the written data is cached to avoid the need for bus traffic upon reading the written register.
+    /// Using the [`WritePolicy::WriteBack`] policy, writes to the output and configuration registers only update the cache until [`Pca9535Cached::flush()`] is called.
     fn write_halfword(&mut self, register: Register, data: u16) -> Result<(), ExpanderError<E>> {
-        self.i2c
-            .write(
-                self.address,
-                &[register as u8, (data >> 8) as u8, data as u8],
-            )
-            .map_err(ExpanderError::WriteError)?;
+        self.count_operation()?;
+
+        if self.defer_write(register) {
+            self.defer_write(register.get_neighbor());
+            self.set_cached(register, (data >> 8) as u8);
+            self.set_cached(register.get_neighbor(), data as u8);
+            return Ok(());
+        }
+
+        self.bus_write(register, &[(data >> 8) as u8, data as u8])?;
 
         // As the IO Expander does not trigger an interrupt once the polarity inversion register value changes, writes to the polarity inversion registers need a special implementation
         // in order to ensure that the input register cache stays up to date.
@@ -278,12 +858,12 @@ where
         register: Register,
         buffer: &mut u16,
     ) -> Result<(), ExpanderError<E>> {
+        self.count_operation()?;
+
         let mut reg_val: [u8; 2] = [0x00; 2];
 
-        if self.interrupt_pin.is_low().unwrap() && register.is_input() {
-            self.i2c
-                .write_read(self.address, &[register as u8], &mut reg_val)
-                .map_err(ExpanderError::WriteReadError)?;
+        if register.is_input() && self.interrupt_active()? {
+            self.bus_write_read(register, &mut reg_val)?;
 
             self.set_cached(register, reg_val[0]);
             self.set_cached(register.get_neighbor(), reg_val[1]);
@@ -298,10 +878,33 @@ where
     }
 }
 
-impl<I2C, E, IP> StandardExpanderInterface<I2C, E> for Pca9535Cached<I2C, IP>
+impl<I2C, E, IP, C, RP, RD> StandardExpanderInterface<I2C, E> for Pca9535Cached<I2C, IP, C, RP, RD>
+where
+    IP: InputPin,
+    E: Debug,
+    I2C: I2c<Error = E>,
+    C: Chip,
+    RD: DelayNs,
+{
+}
+
+impl<I2C, E, IP, C, RP, RD> SplitPins<I2C> for Pca9535Cached<I2C, IP, C, RP, RD>
 where
     IP: InputPin,
     E: Debug,
     I2C: I2c<Error = E>,
+    C: Chip,
+    RD: DelayNs,
 {
+    type Pins<'a, Io>
+        = ExpanderPins<'a, I2C, Io>
+    where
+        Io: SyncExpander<I2C> + 'a;
+
+    fn split_pins<Io>(expander: &Io) -> Result<Self::Pins<'_, Io>, ExpanderError<E>>
+    where
+        Io: SyncExpander<I2C>,
+    {
+        ExpanderPins::new(expander)
+    }
 }
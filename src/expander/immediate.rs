@@ -1,19 +1,32 @@
 //! Contains the implementation of the Immediate Expander interface.
 use core::fmt::Debug;
 
+use hal::delay::DelayNs;
+use hal::digital::{Error as _, OutputPin};
 use hal::i2c::I2c;
 
-use crate::StandardExpanderInterface;
+use crate::chip::{Chip, Pca9535};
+use crate::{ExpanderPins, HardwareAddress, StandardExpanderInterface};
 
-use super::{Expander, ExpanderError, Register};
+use super::io::SplitPins;
+use super::probe::{probe, Presence};
+use super::retry::{NoDelay, RetryPolicy};
+use super::{
+    check_address, pulse_reset_pin, Expander, ExpanderError, NoResetPin, Register, SyncExpander,
+};
 
 #[derive(Debug)]
-pub struct Pca9535Immediate<I2C>
+pub struct Pca9535Immediate<I2C, C = Pca9535, RP = NoResetPin, RD = NoDelay>
 where
     I2C: I2c,
+    C: Chip,
 {
     address: u8,
     i2c: I2C,
+    chip: C,
+    reset_pin: RP,
+    retry_policy: RetryPolicy<RD>,
+    write_verification: bool,
 }
 
 impl<I2C> Pca9535Immediate<I2C>
@@ -24,30 +37,229 @@ where
     ///
     /// # Panics
     /// If the given device hardware address is outside the permittable range of `32-39`.
+    /// Use [`Pca9535Immediate::from_hardware_address()`] to create the instance from an address which is guaranteed to be valid, or [`Pca9535Immediate::try_new()`] to handle an invalid address as error.
     pub fn new(i2c: I2C, address: u8) -> Self {
-        assert!(address > 31 && address < 40);
+        assert!(Pca9535::is_valid_address(address));
 
-        Self { address, i2c }
+        Self {
+            address,
+            i2c,
+            chip: Pca9535,
+            reset_pin: NoResetPin,
+            retry_policy: RetryPolicy::default(),
+            write_verification: false,
+        }
+    }
+
+    /// Creates a new immediate PCA9535 instance, returning [`ExpanderError::InvalidAddress`] instead of panicking if the given device hardware address is outside the permittable range of `32-39`.
+    pub fn try_new(i2c: I2C, address: u8) -> Result<Self, ExpanderError<I2C::Error>> {
+        Self::with_chip(i2c, address, Pca9535)
+    }
+
+    /// Creates a new immediate PCA9535 instance using the given [`HardwareAddress`], which is always within the permittable range.
+    pub fn from_hardware_address(i2c: I2C, address: HardwareAddress) -> Self {
+        Self::new(i2c, address.address())
+    }
+}
+
+impl<I2C, C> Pca9535Immediate<I2C, C>
+where
+    I2C: I2c,
+    C: Chip,
+{
+    /// Creates a new immediate instance of the given chip variant.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidAddress`] if the given device hardware address is outside the permittable range of the chip variant.
+    ///
+    /// # Compile errors
+    /// The chip variant needs to be a 16-bit expander (see [`Chip::BANKS`]).
+    pub fn with_chip(i2c: I2C, address: u8, chip: C) -> Result<Self, ExpanderError<I2C::Error>> {
+        const { assert!(C::BANKS == 2, "the chip variant is not a 16-bit expander") };
+
+        check_address::<C, _>(address)?;
+
+        Ok(Self {
+            address,
+            i2c,
+            chip,
+            reset_pin: NoResetPin,
+            retry_policy: RetryPolicy::default(),
+            write_verification: false,
+        })
+    }
+}
+
+impl<I2C, C, RD> Pca9535Immediate<I2C, C, NoResetPin, RD>
+where
+    I2C: I2c,
+    C: Chip,
+{
+    /// Hands over the pin connected to the active-low RESET input of the device, which enables [`Pca9535Immediate::reset()`].
+    ///
+    /// The pin is driven `high` right away to release the device from reset.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::ResetPinError`] if setting the state of the reset pin failed.
+    ///
+    /// # Compile errors
+    /// The chip variant needs to provide a RESET input (see [`Chip::HAS_RESET_PIN`]).
+    pub fn with_reset_pin<RP>(
+        self,
+        mut reset_pin: RP,
+    ) -> Result<Pca9535Immediate<I2C, C, RP, RD>, ExpanderError<I2C::Error>>
+    where
+        RP: OutputPin,
+    {
+        const { assert!(C::HAS_RESET_PIN, "the chip variant has no RESET input") };
+
+        reset_pin
+            .set_high()
+            .map_err(|err| ExpanderError::ResetPinError(err.kind()))?;
+
+        Ok(Pca9535Immediate {
+            address: self.address,
+            i2c: self.i2c,
+            chip: self.chip,
+            reset_pin,
+            retry_policy: self.retry_policy,
+            write_verification: self.write_verification,
+        })
+    }
+}
+
+impl<I2C, C, RP, RD> Pca9535Immediate<I2C, C, RP, RD>
+where
+    I2C: I2c,
+    C: Chip,
+    RD: DelayNs,
+{
+    /// Returns the chip variant of the expander.
+    pub fn chip(&self) -> C {
+        self.chip
+    }
+
+    /// Checks whether the device answers on the configured address, see [`crate::probe()`].
+    pub fn probe(&mut self) -> Result<Presence, ExpanderError<I2C::Error>> {
+        probe(&mut self.i2c, self.address)
     }
 
     /// Destroys the expander struct, returning the contained I2C
+    ///
+    /// Use [`Pca9535Immediate::release_reset_pin()`] beforehand to retrieve the reset pin.
     pub fn destroy(self) -> I2C {
         self.i2c
     }
+
+    /// Returns the reset pin handed over using [`Pca9535Immediate::with_reset_pin()`] together with the expander.
+    pub fn release_reset_pin(self) -> (Pca9535Immediate<I2C, C, NoResetPin, RD>, RP) {
+        (
+            Pca9535Immediate {
+                address: self.address,
+                i2c: self.i2c,
+                chip: self.chip,
+                reset_pin: NoResetPin,
+                retry_policy: self.retry_policy,
+                write_verification: self.write_verification,
+            },
+            self.reset_pin,
+        )
+    }
+
+    /// Sets the policy on retrying failed bus transactions, see [`RetryPolicy`].
+    pub fn with_retry_policy<RD2>(
+        self,
+        retry_policy: RetryPolicy<RD2>,
+    ) -> Pca9535Immediate<I2C, C, RP, RD2>
+    where
+        RD2: DelayNs,
+    {
+        Pca9535Immediate {
+            address: self.address,
+            i2c: self.i2c,
+            chip: self.chip,
+            reset_pin: self.reset_pin,
+            retry_policy,
+            write_verification: self.write_verification,
+        }
+    }
+
+    /// Enables or disables the verification of writes to the output and configuration registers.
+    ///
+    /// If enabled, each written output or configuration register is read back in a separate bus transaction. A value differing from the written one is reported
+    /// as [`ExpanderError::WriteVerifyMismatch`].
+    pub fn set_write_verification(&mut self, enabled: bool) {
+        self.write_verification = enabled;
+    }
+
+    /// Reads back the registers written with the given data and compares them, if write verification is enabled for the register.
+    fn verify_write(
+        &mut self,
+        register: Register,
+        data: &[u8],
+    ) -> Result<(), ExpanderError<I2C::Error>> {
+        if !self.write_verification || !register.is_output_or_configuration() {
+            return Ok(());
+        }
+
+        let address = self.address;
+        let mut buf: [u8; 2] = [0x00; 2];
+        let actual = &mut buf[..data.len()];
+
+        self.retry_policy
+            .run(&mut self.i2c, |i2c| {
+                i2c.write_read(address, &[register as u8], actual)
+            })
+            .map_err(ExpanderError::WriteReadError)?;
+
+        for ((register, expected), actual) in [register, register.get_neighbor()]
+            .into_iter()
+            .zip(data)
+            .zip(actual.iter())
+        {
+            if expected != actual {
+                return Err(ExpanderError::WriteVerifyMismatch {
+                    register,
+                    expected: *expected,
+                    actual: *actual,
+                });
+            }
+        }
+
+        Ok(())
+    }
 }
 
-impl<I2C, E> Expander<I2C> for Pca9535Immediate<I2C>
+impl<I2C, C, RP, RD> Pca9535Immediate<I2C, C, RP, RD>
+where
+    I2C: I2c,
+    C: Chip,
+    RP: OutputPin,
+{
+    /// Resets the device to its power-on state by pulsing its RESET input.
+    pub fn reset<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), ExpanderError<I2C::Error>> {
+        pulse_reset_pin(&mut self.reset_pin, delay)
+    }
+}
+
+impl<I2C, E, C, RP, RD> Expander<I2C> for Pca9535Immediate<I2C, C, RP, RD>
 where
     E: Debug,
     I2C: I2c<Error = E>,
+    C: Chip,
+    RD: DelayNs,
 {
     /// Writes one byte to the given register
     ///
     /// Only use this function if you really have to. For most use cases, the crate provides simpler ways of interacting with the device.
     fn write_byte(&mut self, register: Register, data: u8) -> Result<(), ExpanderError<E>> {
-        self.i2c
-            .write(self.address, &[register as u8, data])
-            .map_err(ExpanderError::WriteError)
+        self.retry_policy
+            .run(&mut self.i2c, |i2c| {
+                i2c.write(self.address, &[register as u8, data])
+            })
+            .map_err(ExpanderError::WriteError)?;
+
+        self.verify_write(register, &[data])
     }
 
     /// Reads one byte of the given register
@@ -56,8 +268,10 @@ where
     fn read_byte(&mut self, register: Register, buffer: &mut u8) -> Result<(), ExpanderError<E>> {
         let mut buf = [0_u8];
 
-        self.i2c
-            .write_read(self.address, &[register as u8], &mut buf)
+        self.retry_policy
+            .run(&mut self.i2c, |i2c| {
+                i2c.write_read(self.address, &[register as u8], &mut buf)
+            })
             .map_err(ExpanderError::WriteReadError)?;
 
         *buffer = buf[0];
@@ -72,12 +286,16 @@ where
     /// # Register pairs
     /// Please see [`Register`] for more information about the register pairs and how they affect the half-word read and write functions.
     fn write_halfword(&mut self, register: Register, data: u16) -> Result<(), ExpanderError<E>> {
-        self.i2c
-            .write(
-                self.address,
-                &[register as u8, (data >> 8) as u8, data as u8],
-            )
-            .map_err(ExpanderError::WriteError)
+        self.retry_policy
+            .run(&mut self.i2c, |i2c| {
+                i2c.write(
+                    self.address,
+                    &[register as u8, (data >> 8) as u8, data as u8],
+                )
+            })
+            .map_err(ExpanderError::WriteError)?;
+
+        self.verify_write(register, &[(data >> 8) as u8, data as u8])
     }
 
     /// Reads one halfword of the given register
@@ -93,8 +311,10 @@ where
     ) -> Result<(), ExpanderError<E>> {
         let mut reg_val: [u8; 2] = [0x00; 2];
 
-        self.i2c
-            .write_read(self.address, &[register as u8], &mut reg_val)
+        self.retry_policy
+            .run(&mut self.i2c, |i2c| {
+                i2c.write_read(self.address, &[register as u8], &mut reg_val)
+            })
             .map_err(ExpanderError::WriteReadError)?;
 
         *buffer = (reg_val[0] as u16) << 8 | reg_val[1] as u16;
@@ -103,9 +323,31 @@ where
     }
 }
 
-impl<I2C, E> StandardExpanderInterface<I2C, E> for Pca9535Immediate<I2C>
+impl<I2C, E, C, RP, RD> StandardExpanderInterface<I2C, E> for Pca9535Immediate<I2C, C, RP, RD>
 where
     E: Debug,
     I2C: I2c<Error = E>,
+    C: Chip,
+    RD: DelayNs,
 {
 }
+
+impl<I2C, E, C, RP, RD> SplitPins<I2C> for Pca9535Immediate<I2C, C, RP, RD>
+where
+    E: Debug,
+    I2C: I2c<Error = E>,
+    C: Chip,
+    RD: DelayNs,
+{
+    type Pins<'a, Io>
+        = ExpanderPins<'a, I2C, Io>
+    where
+        Io: SyncExpander<I2C> + 'a;
+
+    fn split_pins<Io>(expander: &Io) -> Result<Self::Pins<'_, Io>, ExpanderError<E>>
+    where
+        Io: SyncExpander<I2C>,
+    {
+        ExpanderPins::new(expander)
+    }
+}
@@ -1,10 +1,128 @@
 //! Contains the implementation to make an [`Expander`] Sync.
+use core::fmt::Debug;
 use core::marker::PhantomData;
 
 use hal::i2c::{ErrorType, I2c};
 
 use super::{Expander, ExpanderError, Register, SyncExpander};
-use crate::ExpanderMutex;
+use crate::{ExpanderMutex, GPIOBank, LockError, PinId, PinState};
+
+/// Output changes of multiple pins collected by [`IoExpander::transaction()`].
+///
+/// Changes are only recorded by this struct and written to the device at once when the transaction is committed. If the same pin is changed multiple times, the last change wins.
+///
+/// If a pin outside the allowed range of 0-7 is passed to one of the functions, the transaction is discarded and [`IoExpander::transaction()`] returns
+/// [`ExpanderError::InvalidPin`] containing the first invalid pin.
+#[derive(Debug, Default)]
+pub struct IoTransaction {
+    high: u16,
+    low: u16,
+    invalid_pin: Option<u8>,
+}
+
+impl IoTransaction {
+    /// Drives the given pin high once the transaction is committed.
+    pub fn set_high(&mut self, bank: GPIOBank, pin: u8) -> &mut Self {
+        self.set_state(bank, pin, PinState::High)
+    }
+
+    /// Drives the given pin low once the transaction is committed.
+    pub fn set_low(&mut self, bank: GPIOBank, pin: u8) -> &mut Self {
+        self.set_state(bank, pin, PinState::Low)
+    }
+
+    /// Drives the given pin to the given state once the transaction is committed.
+    pub fn set_state(&mut self, bank: GPIOBank, pin: u8, state: PinState) -> &mut Self {
+        match PinId::new(bank, pin) {
+            Some(pin) => self.set_pin(pin, state),
+            None => {
+                self.invalid_pin.get_or_insert(pin);
+                self
+            }
+        }
+    }
+
+    /// Drives the pin with the given [`PinId`] to the given state once the transaction is committed.
+    pub fn set_pin(&mut self, pin: PinId, state: PinState) -> &mut Self {
+        let mask = pin.mask();
+
+        match state {
+            PinState::High => {
+                self.high |= mask;
+                self.low &= !mask;
+            }
+            PinState::Low => {
+                self.low |= mask;
+                self.high &= !mask;
+            }
+        }
+
+        self
+    }
+
+    /// Writes the collected changes to the device, issuing one register write per affected bank or a single register pair write if both banks are affected.
+    fn commit<I2C, E, Ex>(&self, expander: &mut Ex) -> Result<(), ExpanderError<E>>
+    where
+        E: Debug,
+        I2C: I2c<Error = E>,
+        Ex: Expander<I2C>,
+    {
+        if let Some(pin) = self.invalid_pin {
+            return Err(ExpanderError::InvalidPin(pin));
+        }
+
+        let changed = self.high | self.low;
+
+        if changed & 0x00FF != 0 && changed & 0xFF00 != 0 {
+            let mut reg_val: u16 = 0x0000;
+
+            expander.read_halfword(Register::OutputPort0, &mut reg_val)?;
+
+            let outputs = (reg_val.swap_bytes() | self.high) & !self.low;
+
+            return expander.write_halfword(Register::OutputPort0, outputs.swap_bytes());
+        }
+
+        for (bank, register) in [
+            (GPIOBank::Bank0, Register::OutputPort0),
+            (GPIOBank::Bank1, Register::OutputPort1),
+        ] {
+            let shift = bank as u8 * 8;
+
+            if (changed >> shift) as u8 != 0 {
+                let mut reg_val: u8 = 0x00;
+
+                expander.read_byte(register, &mut reg_val)?;
+
+                expander.write_byte(
+                    register,
+                    (reg_val | (self.high >> shift) as u8) & !(self.low >> shift) as u8,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Expanders which can be split into their pins by [`IoExpander::split()`].
+///
+/// The 16-bit expanders are split into [`crate::ExpanderPins`] and the 8-bit [`crate::Pca9554Immediate`] into [`crate::ExpanderPins8`], so using a pin the device does not
+/// provide is a compile-time error.
+pub trait SplitPins<I2C>: Expander<I2C>
+where
+    I2C: I2c,
+{
+    /// The pins of the expander.
+    type Pins<'a, Io>
+    where
+        Io: SyncExpander<I2C> + 'a;
+
+    /// Configures all pins of the device as inputs and creates their pin structs using the given expander.
+    fn split_pins<Io>(expander: &Io) -> Result<Self::Pins<'_, Io>, ExpanderError<I2C::Error>>
+    where
+        Io: SyncExpander<I2C>;
+}
 
 /// A wrapper struct to make an Expander Sync.
 /// This Expander type can be used to generate [`crate::ExpanderInputPin`] or [`crate::ExpanderOutputPin`].
@@ -16,8 +134,8 @@ where
     Em: ExpanderMutex<Ex>,
 {
     expander_mutex: Em,
-    phantom_data: PhantomData<Ex>,
-    phantom_data_2: PhantomData<I2C>,
+    phantom_data: PhantomData<fn() -> Ex>,
+    phantom_data_2: PhantomData<fn() -> I2C>,
 }
 
 impl<I2C, Em, Ex> IoExpander<I2C, Ex, Em>
@@ -34,6 +152,97 @@ where
             phantom_data_2: PhantomData,
         }
     }
+
+    /// Locks the expander and calls the given closure with exclusive access to it.
+    ///
+    /// This allows to use functionality of the wrapped expander which is not part of the [`Expander`] trait, like [`crate::Pca9535Cached::flush()`].
+    /// The closure must not use any pins of this expander, as the expander is locked during its execution.
+    ///
+    /// # Errors
+    /// Returns a [`LockError`] if the expander could not be locked, in which case the closure is not called.
+    pub fn with_expander<R, C: FnOnce(&mut Ex) -> R>(&self, c: C) -> Result<R, LockError> {
+        self.expander_mutex.lock(c)
+    }
+}
+
+impl<I2C, E, Em, Ex> IoExpander<I2C, Ex, Em>
+where
+    E: Debug,
+    I2C: I2c<Error = E>,
+    Em: ExpanderMutex<Ex>,
+    Ex: Expander<I2C> + Send,
+{
+    /// Changes the output state of multiple pins at once.
+    ///
+    /// The expander stays locked while the given closure collects the pin changes in an [`IoTransaction`]. Afterwards, the changes are written to the device using
+    /// one register write per affected bank, so all pins of a bank switch at the same time without intermediate states being visible on the bus.
+    ///
+    /// The closure must not use any pins of this expander, as the expander is locked during its execution.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] without writing any changes if a pin outside the allowed range of 0-7 was passed to the [`IoTransaction`].
+    /// ```no_run
+    /// use std::sync::Mutex;
+    /// use rppal::i2c::I2c;
+    /// use pca9535::{GPIOBank, IoExpander, Pca9535Immediate};
+    ///
+    /// let i2c = I2c::new().unwrap();
+    /// let expander = Pca9535Immediate::new(i2c, 32);
+    ///
+    /// let io_expander: IoExpander<_, _, Mutex<_>> = IoExpander::new(expander);
+    ///
+    /// // H-bridge enable + direction
+    /// io_expander
+    ///     .transaction(|transaction| {
+    ///         transaction
+    ///             .set_high(GPIOBank::Bank0, 0)
+    ///             .set_low(GPIOBank::Bank0, 1);
+    ///     })
+    ///     .unwrap();
+    /// ```
+    pub fn transaction<R, C: FnOnce(&mut IoTransaction) -> R>(
+        &self,
+        c: C,
+    ) -> Result<R, ExpanderError<E>> {
+        self.expander_mutex
+            .lock(|ex| {
+                let mut transaction = IoTransaction::default();
+                let result = c(&mut transaction);
+
+                transaction.commit(ex)?;
+
+                Ok(result)
+            })
+            .map_err(ExpanderError::LockError)?
+    }
+
+    /// Splits the expander into its pins, each handed out exactly once.
+    ///
+    /// 16-bit expanders are split into [`crate::ExpanderPins`], while the 8-bit [`crate::Pca9554Immediate`] is split into [`crate::ExpanderPins8`], which only contains the pins
+    /// of [`GPIOBank::Bank0`]. All pins are configured as inputs by this function and returned as [`crate::ExpanderInputPin`]. They can be converted into outputs and back using
+    /// [`crate::ExpanderInputPin::into_output()`] and [`crate::ExpanderOutputPin::into_input()`]. As the returned pins mutably borrow the expander, no other pins
+    /// can be created for the same expander while they are alive, which makes it impossible for two parts of the code to own the same pin.
+    /// ```no_run
+    /// use std::sync::Mutex;
+    /// use rppal::i2c::I2c;
+    /// use hal::digital::OutputPin;
+    /// use pca9535::{IoExpander, Pca9535Immediate, PinState};
+    ///
+    /// let i2c = I2c::new().unwrap();
+    /// let expander = Pca9535Immediate::new(i2c, 32);
+    ///
+    /// let mut io_expander: IoExpander<_, _, Mutex<_>> = IoExpander::new(expander);
+    /// let pins = io_expander.split().unwrap();
+    ///
+    /// let mut led = pins.io1_2.into_output(PinState::Low).unwrap();
+    /// led.set_high().unwrap();
+    /// ```
+    pub fn split(&mut self) -> Result<Ex::Pins<'_, Self>, ExpanderError<E>>
+    where
+        Ex: SplitPins<I2C>,
+    {
+        Ex::split_pins(self)
+    }
 }
 
 impl<I2C, Em, Ex> SyncExpander<I2C> for IoExpander<I2C, Ex, Em>
@@ -47,7 +256,9 @@ where
         register: Register,
         data: u8,
     ) -> Result<(), ExpanderError<<I2C as ErrorType>::Error>> {
-        self.expander_mutex.lock(|ex| ex.write_byte(register, data))
+        self.expander_mutex
+            .lock(|ex| ex.write_byte(register, data))
+            .map_err(ExpanderError::LockError)?
     }
     fn read_byte(
         &self,
@@ -56,6 +267,7 @@ where
     ) -> Result<(), ExpanderError<<I2C as ErrorType>::Error>> {
         self.expander_mutex
             .lock(|ex| ex.read_byte(register, buffer))
+            .map_err(ExpanderError::LockError)?
     }
     fn write_halfword(
         &self,
@@ -64,6 +276,7 @@ where
     ) -> Result<(), ExpanderError<<I2C as ErrorType>::Error>> {
         self.expander_mutex
             .lock(|ex| ex.write_halfword(register, data))
+            .map_err(ExpanderError::LockError)?
     }
     fn read_halfword(
         &self,
@@ -72,5 +285,23 @@ where
     ) -> Result<(), ExpanderError<<I2C as ErrorType>::Error>> {
         self.expander_mutex
             .lock(|ex| ex.read_halfword(register, buffer))
+            .map_err(ExpanderError::LockError)?
+    }
+    fn modify_byte<F>(
+        &self,
+        register: Register,
+        f: F,
+    ) -> Result<(), ExpanderError<<I2C as ErrorType>::Error>>
+    where
+        F: FnOnce(u8) -> u8,
+    {
+        self.expander_mutex
+            .lock(|ex| {
+                let mut reg_val: u8 = 0x00;
+
+                ex.read_byte(register, &mut reg_val)?;
+                ex.write_byte(register, f(reg_val))
+            })
+            .map_err(ExpanderError::LockError)?
     }
 }
@@ -1,13 +1,26 @@
 //! Contains all available Expander interfaces and traits.
 use core::fmt::Debug;
 
+use hal::delay::DelayNs;
+use hal::digital::{Error as _, OutputPin};
 use hal::i2c::{ErrorType, I2c};
 
-use super::{GPIOBank, Register};
+use super::{Chip, GPIOBank, Register};
+use crate::mutex::LockError;
 
+pub mod agile;
+#[cfg(feature = "async")]
+pub mod asynch;
 pub mod cached;
 pub mod immediate;
 pub mod io;
+pub mod multi;
+pub mod pca9554;
+pub mod pcal9535a;
+pub mod polling;
+pub mod probe;
+pub mod retry;
+pub mod shared;
 pub mod standard;
 
 /// Trait for standard IO expanders which are not Sync
@@ -63,39 +76,175 @@ where
         register: Register,
         buffer: &mut u16,
     ) -> Result<(), ExpanderError<<I2C as ErrorType>::Error>>;
+
+    /// Reads the given register, applies the given function to its value and writes the result back.
+    ///
+    /// Implementations which lock the expander on each access, like [`crate::IoExpander`] and [`crate::MultiExpanderDevice`], keep it locked for the whole
+    /// read-modify-write, so concurrent changes to other pins of the same port are not lost.
+    fn modify_byte<F>(
+        &self,
+        register: Register,
+        f: F,
+    ) -> Result<(), ExpanderError<<I2C as ErrorType>::Error>>
+    where
+        F: FnOnce(u8) -> u8,
+    {
+        let mut reg_val: u8 = 0x00;
+
+        self.read_byte(register, &mut reg_val)?;
+        self.write_byte(register, f(reg_val))
+    }
+}
+
+/// Placeholder for the reset pin of expanders which do not control the RESET input of the device.
+///
+/// Use the `with_reset_pin()` function of the expander types to hand over the pin connected to the RESET input, for example [`crate::Pca9535Cached::with_reset_pin()`].
+#[derive(Debug, Default, Copy, Clone)]
+pub struct NoResetPin;
+
+/// Pulses the given active-low reset pin.
+///
+/// The pin is held low and released for 1µs each, which exceeds the minimum reset pulse duration and reset time of the supported devices.
+pub(crate) fn pulse_reset_pin<RP, D, ERR>(
+    reset_pin: &mut RP,
+    delay: &mut D,
+) -> Result<(), ExpanderError<ERR>>
+where
+    RP: OutputPin,
+    D: DelayNs,
+    ERR: Debug,
+{
+    reset_pin
+        .set_low()
+        .map_err(|err| ExpanderError::ResetPinError(err.kind()))?;
+    delay.delay_us(1);
+    reset_pin
+        .set_high()
+        .map_err(|err| ExpanderError::ResetPinError(err.kind()))?;
+    delay.delay_us(1);
+
+    Ok(())
+}
+
+/// Returns [`ExpanderError::InvalidPin`] if the given pin is not in the allowed range of 0-7.
+pub(crate) fn check_pin<ERR: Debug>(pin: u8) -> Result<(), ExpanderError<ERR>> {
+    if pin < 8 {
+        Ok(())
+    } else {
+        Err(ExpanderError::InvalidPin(pin))
+    }
 }
 
+/// Returns [`ExpanderError::InvalidAddress`] if the given device hardware address is outside the permittable range of the chip variant.
+pub(crate) fn check_address<C: Chip, ERR: Debug>(address: u8) -> Result<(), ExpanderError<ERR>> {
+    if C::is_valid_address(address) {
+        Ok(())
+    } else {
+        Err(ExpanderError::InvalidAddress(address))
+    }
+}
+
+/// Errors reported by the expanders and hal pins
 #[derive(Debug)]
 pub enum ExpanderError<ERR>
 where
     ERR: Debug,
 {
+    /// An I2C write transaction failed
     WriteError(ERR),
+    /// An I2C write-read transaction failed
     WriteReadError(ERR),
+    /// Reading the state of the interrupt pin failed
+    InterruptPinError(hal::digital::ErrorKind),
+    /// Setting the state of the reset pin failed
+    ResetPinError(hal::digital::ErrorKind),
+    /// The given pin is not in the allowed range
+    InvalidPin(u8),
+    /// The given device hardware address is outside the permittable range
+    InvalidAddress(u8),
+    /// The register is not available on the device, like the registers of [`GPIOBank::Bank1`] on 8-bit expanders
+    UnsupportedRegister(Register),
+    /// The pin is not configured in the direction required by the requested operation
+    DirectionMismatch,
+    /// The cached value of a register differs from the value read from the device
+    CacheMismatch {
+        register: Register,
+        cached: u8,
+        actual: u8,
+    },
+    /// The value read back from a register after writing it differs from the written value
+    WriteVerifyMismatch {
+        register: Register,
+        expected: u8,
+        actual: u8,
+    },
+    /// The [`crate::ExpanderMutex`] guarding the expander could not be locked
+    LockError(LockError),
 }
 
-impl<ERR: Debug> hal::digital::Error for ExpanderError<ERR> {
-    fn kind(&self) -> hal::digital::ErrorKind {
-        hal::digital::ErrorKind::Other
+impl<ERR> ExpanderError<ERR>
+where
+    ERR: hal::i2c::Error,
+{
+    /// Returns the [`hal::i2c::ErrorKind`] of the underlying I2C error, or `None` if the error was not caused by the I2C bus.
+    pub fn i2c_error_kind(&self) -> Option<hal::i2c::ErrorKind> {
+        match self {
+            Self::WriteError(err) | Self::WriteReadError(err) => Some(err.kind()),
+            _ => None,
+        }
     }
 }
 
-#[cfg(feature = "std")]
-impl<T> std::fmt::Display for ExpanderError<T>
-where
-    T: Debug,
-{
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({:#?})", self)
+impl<ERR: Debug> hal::digital::Error for ExpanderError<ERR> {
+    fn kind(&self) -> hal::digital::ErrorKind {
+        match self {
+            Self::InterruptPinError(kind) | Self::ResetPinError(kind) => *kind,
+            _ => hal::digital::ErrorKind::Other,
+        }
     }
 }
 
-#[cfg(feature = "std")]
-impl<T> std::error::Error for ExpanderError<T>
+impl<T> core::fmt::Display for ExpanderError<T>
 where
     T: Debug,
 {
-    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        None
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::WriteError(err) => write!(f, "I2C write failed: {:?}", err),
+            Self::WriteReadError(err) => write!(f, "I2C write-read failed: {:?}", err),
+            Self::InterruptPinError(kind) => {
+                write!(f, "reading the interrupt pin failed: {}", kind)
+            }
+            Self::ResetPinError(kind) => write!(f, "setting the reset pin failed: {}", kind),
+            Self::InvalidPin(pin) => write!(f, "pin {} is outside the allowed range", pin),
+            Self::InvalidAddress(address) => {
+                write!(f, "address {} is outside the permittable range", address)
+            }
+            Self::UnsupportedRegister(register) => {
+                write!(f, "register {:?} is not available on this device", register)
+            }
+            Self::DirectionMismatch => write!(f, "pin is not configured in the required direction"),
+            Self::CacheMismatch {
+                register,
+                cached,
+                actual,
+            } => write!(
+                f,
+                "cached value {:#04X} of register {:?} differs from device value {:#04X}",
+                cached, register, actual
+            ),
+            Self::WriteVerifyMismatch {
+                register,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "value {:#04X} read back from register {:?} differs from written value {:#04X}",
+                actual, register, expected
+            ),
+            Self::LockError(err) => write!(f, "locking the expander failed: {}", err),
+        }
     }
 }
+
+impl<T> core::error::Error for ExpanderError<T> where T: Debug {}
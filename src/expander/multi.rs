@@ -0,0 +1,410 @@
+//! Contains the implementation to drive up to eight expanders on one bus.
+use core::fmt::Debug;
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+use hal::i2c::{ErrorType, I2c};
+
+use crate::mutex::ExpanderMutex;
+use crate::{
+    ExpanderPins, GPIOBank, HandleInputPin, HandleOutputPin, PinId, PinState,
+    StandardExpanderInterface,
+};
+
+use super::immediate::Pca9535Immediate;
+use super::io::SplitPins;
+use super::probe::{probe, scan, Presence};
+use super::{Expander, ExpanderError, Register, SyncExpander};
+
+/// Up to eight PCA9535 family expanders sharing one I2C bus.
+///
+/// The devices are addressed by their index `0-7`, which corresponds to the levels of their hardware address pins A2..A0 and therefore to the I2C addresses `32-39`.
+/// Each device is accessed using a [`MultiExpanderDevice`] handle, which implements the [`StandardExpanderInterface`] and [`SyncExpander`] and can therefore be used
+/// to create the hal pins.
+///
+/// # Flat pin namespace
+/// Additionally, all pins of the eight devices are addressable using a flat pin index in the range of `0-127`. Pin `n` is pin `n % 16` of device `n / 16`,
+/// where the pin within the device is numbered as described by [`PinId`].
+/// Each flat pin operation holds the bus lock for its whole read-modify-write cycle, so concurrent updates of different pins of the same port do not overwrite each other.
+/// The hal pins of a flat pin index are created using [`MultiExpander::input_pin()`] and [`MultiExpander::output_pin()`].
+/// ```no_run
+/// use std::sync::Mutex;
+/// use hal::digital::OutputPin;
+/// use rppal::i2c::I2c;
+/// use pca9535::{ExpanderOutputPin, GPIOBank, MultiExpander, PinState};
+///
+/// let i2c = I2c::new().unwrap();
+/// let expanders: MultiExpander<_, Mutex<_>> = MultiExpander::new(i2c);
+///
+/// let present = expanders.scan().unwrap();
+///
+/// expanders.pin_into_output(42).unwrap();
+/// expanders.pin_set_high(42).unwrap();
+///
+/// let mut buzzer = expanders.output_pin(97, PinState::Low).unwrap();
+/// buzzer.set_high().unwrap();
+///
+/// let devices = expanders.devices();
+/// let mut led = ExpanderOutputPin::new(&devices[3], GPIOBank::Bank1, 2, PinState::Low).unwrap();
+/// led.set_high().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct MultiExpander<I2C, M>
+where
+    I2C: I2c + Send,
+    M: ExpanderMutex<I2C>,
+{
+    bus_mutex: M,
+    phantom_data: PhantomData<I2C>,
+}
+
+impl<I2C, E, M> MultiExpander<I2C, M>
+where
+    E: Debug,
+    I2C: I2c<Error = E> + Send,
+    M: ExpanderMutex<I2C>,
+{
+    /// Creates a new multi expander instance owning the given I2C bus.
+    pub fn new(i2c: I2C) -> Self {
+        Self {
+            bus_mutex: M::new(i2c),
+            phantom_data: PhantomData,
+        }
+    }
+
+    /// Detects which devices are present on the bus by probing their addresses, see [`crate::probe()`].
+    ///
+    /// The returned mask holds one bit per device index, which is set if an expander answered on the address of the device.
+    /// Use [`MultiExpander::scan_presence()`] to distinguish missing devices from unrecognized ones.
+    ///
+    /// # Errors
+    /// Bus errors other than a missing acknowledge are returned as [`ExpanderError::WriteReadError`].
+    pub fn scan(&self) -> Result<u8, ExpanderError<E>> {
+        let presences = self.scan_presence()?;
+
+        Ok(presences
+            .iter()
+            .enumerate()
+            .filter(|(_, presence)| presence.is_expander())
+            .fold(0x00, |mask, (index, _)| mask | (0x01 << index)))
+    }
+
+    /// Probes the addresses of all devices and returns their [`Presence`], ordered by the device index.
+    ///
+    /// # Errors
+    /// Bus errors other than a missing acknowledge are returned as [`ExpanderError::WriteReadError`].
+    pub fn scan_presence(&self) -> Result<[Presence; 8], ExpanderError<E>> {
+        self.bus_mutex
+            .lock(|i2c| scan(i2c))
+            .map_err(ExpanderError::LockError)?
+    }
+
+    /// Returns the handle of the device with the given index, or `None` if the index is outside the range of `0-7`.
+    pub fn device(&self, index: u8) -> Option<MultiExpanderDevice<'_, I2C, M>> {
+        (index < 8).then_some(MultiExpanderDevice {
+            bus_mutex: &self.bus_mutex,
+            index,
+            phantom_data: PhantomData,
+        })
+    }
+
+    /// Returns the handles of all eight devices, ordered by their index.
+    pub fn devices(&self) -> [MultiExpanderDevice<'_, I2C, M>; 8] {
+        core::array::from_fn(|index| MultiExpanderDevice {
+            bus_mutex: &self.bus_mutex,
+            index: index as u8,
+            phantom_data: PhantomData,
+        })
+    }
+
+    /// Drives the pin with the given flat index `high`.
+    pub fn pin_set_high(&self, pin: u8) -> Result<(), ExpanderError<E>> {
+        self.with_pin(pin, |ex, bank, pin| ex.pin_set_high(bank, pin))
+    }
+
+    /// Drives the pin with the given flat index `low`.
+    pub fn pin_set_low(&self, pin: u8) -> Result<(), ExpanderError<E>> {
+        self.with_pin(pin, |ex, bank, pin| ex.pin_set_low(bank, pin))
+    }
+
+    /// Toggles the output state of the pin with the given flat index.
+    pub fn pin_toggle(&self, pin: u8) -> Result<(), ExpanderError<E>> {
+        self.with_pin(pin, |ex, bank, pin| ex.pin_toggle(bank, pin))
+    }
+
+    /// Returns `true` if the output of the pin with the given flat index is set `high`.
+    pub fn pin_is_set_high(&self, pin: u8) -> Result<bool, ExpanderError<E>> {
+        self.with_pin(pin, |ex, bank, pin| ex.pin_is_set_high(bank, pin))
+    }
+
+    /// Returns `true` if the input of the pin with the given flat index is `high`.
+    pub fn pin_is_high(&self, pin: u8) -> Result<bool, ExpanderError<E>> {
+        self.with_pin(pin, |ex, bank, pin| ex.pin_is_high(bank, pin))
+    }
+
+    /// Returns `true` if the input of the pin with the given flat index is `low`.
+    pub fn pin_is_low(&self, pin: u8) -> Result<bool, ExpanderError<E>> {
+        self.with_pin(pin, |ex, bank, pin| ex.pin_is_low(bank, pin))
+    }
+
+    /// Configures the pin with the given flat index as input.
+    pub fn pin_into_input(&self, pin: u8) -> Result<(), ExpanderError<E>> {
+        self.with_pin(pin, |ex, bank, pin| ex.pin_into_input(bank, pin))
+    }
+
+    /// Configures the pin with the given flat index as output.
+    pub fn pin_into_output(&self, pin: u8) -> Result<(), ExpanderError<E>> {
+        self.with_pin(pin, |ex, bank, pin| ex.pin_into_output(bank, pin))
+    }
+
+    /// Inverts the input polarity of the pin with the given flat index.
+    pub fn pin_inverse_polarity(&self, pin: u8) -> Result<(), ExpanderError<E>> {
+        self.with_pin(pin, |ex, bank, pin| ex.pin_inverse_polarity(bank, pin))
+    }
+
+    /// Restores the normal input polarity of the pin with the given flat index.
+    pub fn pin_normal_polarity(&self, pin: u8) -> Result<(), ExpanderError<E>> {
+        self.with_pin(pin, |ex, bank, pin| ex.pin_normal_polarity(bank, pin))
+    }
+
+    /// Creates an input pin of the pin with the given flat index and configures it as input.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-127
+    pub fn input_pin(&self, pin: u8) -> Result<MultiInputPin<'_, I2C, M>, ExpanderError<E>> {
+        let (device, pin) = self.locate(pin)?;
+
+        HandleInputPin::from_pin_id(DeviceHandle(device), pin)
+    }
+
+    /// Creates an output pin of the pin with the given flat index, which drives the given initial state.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-127
+    pub fn output_pin(
+        &self,
+        pin: u8,
+        state: PinState,
+    ) -> Result<MultiOutputPin<'_, I2C, M>, ExpanderError<E>> {
+        let (device, pin) = self.locate(pin)?;
+
+        HandleOutputPin::from_pin_id(DeviceHandle(device), pin, state)
+    }
+
+    /// Locks the bus once and calls the given closure with an immediate expander of the device owning the pin with the given flat index,
+    /// so read-modify-write operations of the closure can not interleave with other users of the bus.
+    fn with_pin<T>(
+        &self,
+        pin: u8,
+        c: impl FnOnce(&mut Pca9535Immediate<&mut I2C>, GPIOBank, u8) -> Result<T, ExpanderError<E>>,
+    ) -> Result<T, ExpanderError<E>> {
+        let (device, pin) = self.locate(pin)?;
+
+        device.with_expander(|ex| c(ex, pin.bank(), pin.pin()))
+    }
+
+    /// Returns the device handle and the pin within the device for the given flat pin index.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-127
+    pub fn locate(
+        &self,
+        pin: u8,
+    ) -> Result<(MultiExpanderDevice<'_, I2C, M>, PinId), ExpanderError<E>> {
+        match (self.device(pin / 16), PinId::from_index(pin % 16)) {
+            (Some(device), Some(pin_id)) => Ok((device, pin_id)),
+            _ => Err(ExpanderError::InvalidPin(pin)),
+        }
+    }
+}
+
+/// Handle of a single device of a [`MultiExpander`].
+///
+/// The handle locks the bus of the [`MultiExpander`] on each register access and behaves like a [`crate::Pca9535Immediate`] otherwise.
+/// As the lock is released between register accesses, the read-modify-write functions of the [`StandardExpanderInterface`] are not atomic with respect to other
+/// handles of the same device. Use the flat pin functions of the [`MultiExpander`] or hal pins created from the handle, which keep the bus locked using
+/// [`SyncExpander::modify_byte()`], if multiple threads change pins of the same port.
+#[derive(Debug)]
+pub struct MultiExpanderDevice<'a, I2C, M>
+where
+    I2C: I2c + Send,
+    M: ExpanderMutex<I2C>,
+{
+    bus_mutex: &'a M,
+    index: u8,
+    phantom_data: PhantomData<I2C>,
+}
+
+impl<I2C, M> MultiExpanderDevice<'_, I2C, M>
+where
+    I2C: I2c + Send,
+    M: ExpanderMutex<I2C>,
+{
+    /// Returns the index of the device in the range of `0-7`.
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Returns the 7-bit I2C address of the device.
+    pub fn address(&self) -> u8 {
+        32 + self.index
+    }
+
+    /// Checks whether the device answers on its address, see [`crate::probe()`].
+    pub fn probe(&self) -> Result<Presence, ExpanderError<<I2C as ErrorType>::Error>> {
+        let address = self.address();
+
+        self.bus_mutex
+            .lock(|i2c| probe(i2c, address))
+            .map_err(ExpanderError::LockError)?
+    }
+
+    /// Locks the bus and calls the given closure with an immediate expander of this device.
+    fn with_expander<T>(
+        &self,
+        c: impl FnOnce(&mut Pca9535Immediate<&mut I2C>) -> Result<T, ExpanderError<I2C::Error>>,
+    ) -> Result<T, ExpanderError<I2C::Error>> {
+        self.bus_mutex
+            .lock(|i2c| c(&mut Pca9535Immediate::new(i2c, self.address())))
+            .map_err(ExpanderError::LockError)?
+    }
+}
+
+/// Single input device pin of a [`MultiExpander`] as returned by [`MultiExpander::input_pin()`].
+pub type MultiInputPin<'a, I2C, M> = HandleInputPin<I2C, DeviceHandle<'a, I2C, M>>;
+
+/// Single output device pin of a [`MultiExpander`] as returned by [`MultiExpander::output_pin()`].
+pub type MultiOutputPin<'a, I2C, M> = HandleOutputPin<I2C, DeviceHandle<'a, I2C, M>>;
+
+/// Owned [`MultiExpanderDevice`] used as the handle of the hal pins created by the [`MultiExpander`].
+///
+/// As the device handle only refers to the bus of the [`MultiExpander`], the pins do not need to borrow a handle kept elsewhere.
+#[derive(Debug)]
+pub struct DeviceHandle<'a, I2C, M>(MultiExpanderDevice<'a, I2C, M>)
+where
+    I2C: I2c + Send,
+    M: ExpanderMutex<I2C>;
+
+impl<'a, I2C, M> Deref for DeviceHandle<'a, I2C, M>
+where
+    I2C: I2c + Send,
+    M: ExpanderMutex<I2C>,
+{
+    type Target = MultiExpanderDevice<'a, I2C, M>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<I2C, M> SyncExpander<I2C> for MultiExpanderDevice<'_, I2C, M>
+where
+    I2C: I2c + Send,
+    M: ExpanderMutex<I2C>,
+{
+    fn write_byte(
+        &self,
+        register: Register,
+        data: u8,
+    ) -> Result<(), ExpanderError<<I2C as ErrorType>::Error>> {
+        self.with_expander(|ex| ex.write_byte(register, data))
+    }
+    fn read_byte(
+        &self,
+        register: Register,
+        buffer: &mut u8,
+    ) -> Result<(), ExpanderError<<I2C as ErrorType>::Error>> {
+        self.with_expander(|ex| ex.read_byte(register, buffer))
+    }
+    fn write_halfword(
+        &self,
+        register: Register,
+        data: u16,
+    ) -> Result<(), ExpanderError<<I2C as ErrorType>::Error>> {
+        self.with_expander(|ex| ex.write_halfword(register, data))
+    }
+    fn read_halfword(
+        &self,
+        register: Register,
+        buffer: &mut u16,
+    ) -> Result<(), ExpanderError<<I2C as ErrorType>::Error>> {
+        self.with_expander(|ex| ex.read_halfword(register, buffer))
+    }
+    fn modify_byte<F>(
+        &self,
+        register: Register,
+        f: F,
+    ) -> Result<(), ExpanderError<<I2C as ErrorType>::Error>>
+    where
+        F: FnOnce(u8) -> u8,
+    {
+        self.with_expander(|ex| {
+            let mut reg_val: u8 = 0x00;
+
+            ex.read_byte(register, &mut reg_val)?;
+            ex.write_byte(register, f(reg_val))
+        })
+    }
+}
+
+impl<I2C, M> Expander<I2C> for MultiExpanderDevice<'_, I2C, M>
+where
+    I2C: I2c + Send,
+    M: ExpanderMutex<I2C>,
+{
+    fn write_byte(
+        &mut self,
+        register: Register,
+        data: u8,
+    ) -> Result<(), ExpanderError<<I2C as ErrorType>::Error>> {
+        SyncExpander::write_byte(self, register, data)
+    }
+    fn read_byte(
+        &mut self,
+        register: Register,
+        buffer: &mut u8,
+    ) -> Result<(), ExpanderError<<I2C as ErrorType>::Error>> {
+        SyncExpander::read_byte(self, register, buffer)
+    }
+    fn write_halfword(
+        &mut self,
+        register: Register,
+        data: u16,
+    ) -> Result<(), ExpanderError<<I2C as ErrorType>::Error>> {
+        SyncExpander::write_halfword(self, register, data)
+    }
+    fn read_halfword(
+        &mut self,
+        register: Register,
+        buffer: &mut u16,
+    ) -> Result<(), ExpanderError<<I2C as ErrorType>::Error>> {
+        SyncExpander::read_halfword(self, register, buffer)
+    }
+}
+
+impl<I2C, E, M> StandardExpanderInterface<I2C, E> for MultiExpanderDevice<'_, I2C, M>
+where
+    E: Debug,
+    I2C: I2c<Error = E> + Send,
+    M: ExpanderMutex<I2C>,
+{
+}
+
+impl<I2C, E, M> SplitPins<I2C> for MultiExpanderDevice<'_, I2C, M>
+where
+    E: Debug,
+    I2C: I2c<Error = E> + Send,
+    M: ExpanderMutex<I2C>,
+{
+    type Pins<'a, Io>
+        = ExpanderPins<'a, I2C, Io>
+    where
+        Io: SyncExpander<I2C> + 'a;
+
+    fn split_pins<Io>(expander: &Io) -> Result<Self::Pins<'_, Io>, ExpanderError<E>>
+    where
+        Io: SyncExpander<I2C>,
+    {
+        ExpanderPins::new(expander)
+    }
+}
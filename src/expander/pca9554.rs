@@ -0,0 +1,219 @@
+//! Contains the implementation of the 8-bit Immediate Expander interface.
+use core::fmt::Debug;
+
+use hal::i2c::I2c;
+
+use crate::chip::{Chip, Pca9554};
+use crate::{ExpanderPins8, GPIOBank, StandardExpanderInterface};
+
+use super::io::SplitPins;
+use super::{check_address, Expander, ExpanderError, Register, SyncExpander};
+
+/// Immediate expander interface of the 8-bit PCA9534, PCA9554 and TCA9554.
+///
+/// The 8-bit expanders provide the registers of [`GPIOBank::Bank0`] only, which are mapped onto the [`Register`]s of the 16-bit devices. This allows to use the
+/// expander with the [`StandardExpanderInterface`], [`crate::IoExpander`] and the hal pins just like a [`crate::Pca9535Immediate`].
+///
+/// # Bank 1
+/// Byte accesses to the registers of [`GPIOBank::Bank1`] return [`ExpanderError::UnsupportedRegister`], so do all single pin functions of [`GPIOBank::Bank1`].
+/// Halfword accesses only transfer the [`GPIOBank::Bank0`] member of the register pair: the [`GPIOBank::Bank1`] part reads as `0x00` and writing a halfword
+/// with a non-zero [`GPIOBank::Bank1`] part returns [`ExpanderError::UnsupportedRegister`]. [`crate::IoExpander::split()`] only hands out the pins of
+/// [`GPIOBank::Bank0`].
+#[derive(Debug)]
+pub struct Pca9554Immediate<I2C, C = Pca9554>
+where
+    I2C: I2c,
+    C: Chip,
+{
+    address: u8,
+    i2c: I2C,
+    chip: C,
+}
+
+impl<I2C> Pca9554Immediate<I2C>
+where
+    I2C: I2c,
+{
+    /// Creates a new immediate PCA9554 instance.
+    ///
+    /// # Panics
+    /// If the given device hardware address is outside the permittable range of `32-39`.
+    /// Use [`Pca9554Immediate::try_new()`] to handle an invalid address as error.
+    pub fn new(i2c: I2C, address: u8) -> Self {
+        assert!(Pca9554::is_valid_address(address));
+
+        Self {
+            address,
+            i2c,
+            chip: Pca9554,
+        }
+    }
+
+    /// Creates a new immediate PCA9554 instance, returning [`ExpanderError::InvalidAddress`] instead of panicking if the given device hardware address is outside the permittable range of `32-39`.
+    pub fn try_new(i2c: I2C, address: u8) -> Result<Self, ExpanderError<I2C::Error>> {
+        Self::with_chip(i2c, address, Pca9554)
+    }
+}
+
+impl<I2C, C> Pca9554Immediate<I2C, C>
+where
+    I2C: I2c,
+    C: Chip,
+{
+    /// Creates a new immediate instance of the given 8-bit chip variant.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidAddress`] if the given device hardware address is outside the permittable range of the chip variant.
+    ///
+    /// # Compile errors
+    /// The chip variant needs to be an 8-bit expander (see [`Chip::BANKS`]).
+    pub fn with_chip(i2c: I2C, address: u8, chip: C) -> Result<Self, ExpanderError<I2C::Error>> {
+        const { assert!(C::BANKS == 1, "the chip variant is not an 8-bit expander") };
+
+        check_address::<C, _>(address)?;
+
+        Ok(Self { address, i2c, chip })
+    }
+
+    /// Returns the chip variant of the expander.
+    pub fn chip(&self) -> C {
+        self.chip
+    }
+
+    /// Destroys the expander struct, returning the contained I2C
+    pub fn destroy(self) -> I2C {
+        self.i2c
+    }
+
+    /// Returns the command byte of the given [`GPIOBank::Bank0`] register.
+    fn command(register: Register) -> Result<u8, ExpanderError<I2C::Error>> {
+        match register.bank() {
+            GPIOBank::Bank0 => Ok(register as u8 / 2),
+            GPIOBank::Bank1 => Err(ExpanderError::UnsupportedRegister(register)),
+        }
+    }
+}
+
+impl<I2C, E, C> Expander<I2C> for Pca9554Immediate<I2C, C>
+where
+    E: Debug,
+    I2C: I2c<Error = E>,
+    C: Chip,
+{
+    /// Writes one byte to the given register
+    ///
+    /// Only use this function if you really have to. For most use cases, the crate provides simpler ways of interacting with the device.
+    fn write_byte(&mut self, register: Register, data: u8) -> Result<(), ExpanderError<E>> {
+        let command = Self::command(register)?;
+
+        self.i2c
+            .write(self.address, &[command, data])
+            .map_err(ExpanderError::WriteError)
+    }
+
+    /// Reads one byte of the given register
+    ///
+    /// Only use this function if you really have to. For most use cases, the crate provides simpler ways of interacting with the device.
+    fn read_byte(&mut self, register: Register, buffer: &mut u8) -> Result<(), ExpanderError<E>> {
+        let command = Self::command(register)?;
+
+        self.i2c
+            .write_read(self.address, &[command], core::slice::from_mut(buffer))
+            .map_err(ExpanderError::WriteReadError)
+    }
+
+    /// Writes one halfword to the given register
+    ///
+    /// Only use this function if you really have to. For most use cases, the crate provides simpler ways of interacting with the device.
+    ///
+    /// # Register pairs
+    /// Please see [`Register`] for more information about the register pairs and how they affect the half-word read and write functions.
+    /// Only the [`GPIOBank::Bank0`] member of the register pair is written.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::UnsupportedRegister`] containing the [`GPIOBank::Bank1`] member of the register pair without writing anything, if the
+    /// [`GPIOBank::Bank1`] part of the halfword is not `0x00`.
+    fn write_halfword(&mut self, register: Register, data: u16) -> Result<(), ExpanderError<E>> {
+        let (bank0, bank1, bank0_data, bank1_data) = match register.bank() {
+            GPIOBank::Bank0 => (
+                register,
+                register.get_neighbor(),
+                (data >> 8) as u8,
+                data as u8,
+            ),
+            GPIOBank::Bank1 => (
+                register.get_neighbor(),
+                register,
+                data as u8,
+                (data >> 8) as u8,
+            ),
+        };
+
+        if bank1_data != 0x00 {
+            return Err(ExpanderError::UnsupportedRegister(bank1));
+        }
+
+        self.write_byte(bank0, bank0_data)
+    }
+
+    /// Reads one halfword of the given register
+    ///
+    /// Only use this function if you really have to. For most use cases, the crate provides simpler ways of interacting with the device.
+    ///
+    /// # Register pairs
+    /// Please see [`Register`] for more information about the register pairs and how they affect the half-word read and write functions.
+    /// Only the [`GPIOBank::Bank0`] member of the register pair is read, the [`GPIOBank::Bank1`] member reads as `0x00`.
+    fn read_halfword(
+        &mut self,
+        register: Register,
+        buffer: &mut u16,
+    ) -> Result<(), ExpanderError<E>> {
+        let mut reg_val: u8 = 0x00;
+
+        match register.bank() {
+            GPIOBank::Bank0 => {
+                self.read_byte(register, &mut reg_val)?;
+                *buffer = (reg_val as u16) << 8;
+            }
+            GPIOBank::Bank1 => {
+                self.read_byte(register.get_neighbor(), &mut reg_val)?;
+                *buffer = reg_val as u16;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<I2C, E, C> StandardExpanderInterface<I2C, E> for Pca9554Immediate<I2C, C>
+where
+    E: Debug,
+    I2C: I2c<Error = E>,
+    C: Chip,
+{
+    /// Sets the input polarity of all pins of [`GPIOBank::Bank0`] to inverted.
+    ///
+    /// A logic high voltage applied at an input pin results in a `0` written to the device's input register, thus being registered as `low` by the driver.
+    fn inverse_polarity(&mut self) -> Result<(), ExpanderError<E>> {
+        self.write_byte(Register::PolarityInversionPort0, 0xFF)
+    }
+}
+
+impl<I2C, E, C> SplitPins<I2C> for Pca9554Immediate<I2C, C>
+where
+    E: Debug,
+    I2C: I2c<Error = E>,
+    C: Chip,
+{
+    type Pins<'a, Io>
+        = ExpanderPins8<'a, I2C, Io>
+    where
+        Io: SyncExpander<I2C> + 'a;
+
+    fn split_pins<Io>(expander: &Io) -> Result<Self::Pins<'_, Io>, ExpanderError<E>>
+    where
+        Io: SyncExpander<I2C>,
+    {
+        ExpanderPins8::new(expander)
+    }
+}
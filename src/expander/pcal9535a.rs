@@ -0,0 +1,216 @@
+//! Contains the implementation of the PCAL9535A "Agile I/O" Expander interface.
+use core::fmt::Debug;
+
+use hal::i2c::I2c;
+
+use crate::chip::{Chip, Pca9535};
+use crate::{ExpanderPins, ExtendedRegister, HardwareAddress, StandardExpanderInterface};
+
+use super::agile::{AgileExpander, AgileExpanderInterface};
+use super::io::SplitPins;
+use super::{check_address, Expander, ExpanderError, Register, SyncExpander};
+
+/// Immediate expander interface of the PCAL9535A and PCAL9555A.
+///
+/// Like [`crate::Pca9535Immediate`], this type issues an i2c bus transaction on each function call. In addition to the [`StandardExpanderInterface`] it implements
+/// the [`AgileExpanderInterface`] giving access to the extended "Agile I/O" registers of the device.
+#[derive(Debug)]
+pub struct Pcal9535a<I2C>
+where
+    I2C: I2c,
+{
+    address: u8,
+    i2c: I2C,
+}
+
+impl<I2C> Pcal9535a<I2C>
+where
+    I2C: I2c,
+{
+    /// Creates a new immediate PCAL9535A instance.
+    ///
+    /// # Panics
+    /// If the given device hardware address is outside the permittable range of `32-39`.
+    /// Use [`Pcal9535a::from_hardware_address()`] to create the instance from an address which is guaranteed to be valid, or [`Pcal9535a::try_new()`] to handle an invalid address as error.
+    pub fn new(i2c: I2C, address: u8) -> Self {
+        assert!(Pca9535::is_valid_address(address));
+
+        Self { address, i2c }
+    }
+
+    /// Creates a new immediate PCAL9535A instance, returning [`ExpanderError::InvalidAddress`] instead of panicking if the given device hardware address is outside the permittable range of `32-39`.
+    pub fn try_new(i2c: I2C, address: u8) -> Result<Self, ExpanderError<I2C::Error>> {
+        check_address::<Pca9535, _>(address)?;
+
+        Ok(Self { address, i2c })
+    }
+
+    /// Creates a new immediate PCAL9535A instance using the given [`HardwareAddress`], which is always within the permittable range.
+    pub fn from_hardware_address(i2c: I2C, address: HardwareAddress) -> Self {
+        Self::new(i2c, address.address())
+    }
+
+    /// Destroys the expander struct, returning the contained I2C
+    pub fn destroy(self) -> I2C {
+        self.i2c
+    }
+
+    fn write(&mut self, command: u8, data: &[u8]) -> Result<(), ExpanderError<I2C::Error>> {
+        let mut buf = [command, 0x00, 0x00];
+        buf[1..=data.len()].copy_from_slice(data);
+
+        self.i2c
+            .write(self.address, &buf[..=data.len()])
+            .map_err(ExpanderError::WriteError)
+    }
+
+    fn read(&mut self, command: u8, buffer: &mut [u8]) -> Result<(), ExpanderError<I2C::Error>> {
+        self.i2c
+            .write_read(self.address, &[command], buffer)
+            .map_err(ExpanderError::WriteReadError)
+    }
+}
+
+impl<I2C, E> Expander<I2C> for Pcal9535a<I2C>
+where
+    E: Debug,
+    I2C: I2c<Error = E>,
+{
+    /// Writes one byte to the given register
+    ///
+    /// Only use this function if you really have to. For most use cases, the crate provides simpler ways of interacting with the device.
+    fn write_byte(&mut self, register: Register, data: u8) -> Result<(), ExpanderError<E>> {
+        self.write(register as u8, &[data])
+    }
+
+    /// Reads one byte of the given register
+    ///
+    /// Only use this function if you really have to. For most use cases, the crate provides simpler ways of interacting with the device.
+    fn read_byte(&mut self, register: Register, buffer: &mut u8) -> Result<(), ExpanderError<E>> {
+        self.read(register as u8, core::slice::from_mut(buffer))
+    }
+
+    /// Writes one halfword to the given register
+    ///
+    /// Only use this function if you really have to. For most use cases, the crate provides simpler ways of interacting with the device.
+    ///
+    /// # Register pairs
+    /// Please see [`Register`] for more information about the register pairs and how they affect the half-word read and write functions.
+    fn write_halfword(&mut self, register: Register, data: u16) -> Result<(), ExpanderError<E>> {
+        self.write(register as u8, &data.to_be_bytes())
+    }
+
+    /// Reads one halfword of the given register
+    ///
+    /// Only use this function if you really have to. For most use cases, the crate provides simpler ways of interacting with the device.
+    ///
+    /// # Register pairs
+    /// Please see [`Register`] for more information about the register pairs and how they affect the half-word read and write functions.
+    fn read_halfword(
+        &mut self,
+        register: Register,
+        buffer: &mut u16,
+    ) -> Result<(), ExpanderError<E>> {
+        let mut reg_val: [u8; 2] = [0x00; 2];
+
+        self.read(register as u8, &mut reg_val)?;
+
+        *buffer = u16::from_be_bytes(reg_val);
+
+        Ok(())
+    }
+}
+
+impl<I2C, E> AgileExpander<I2C> for Pcal9535a<I2C>
+where
+    E: Debug,
+    I2C: I2c<Error = E>,
+{
+    /// Writes one byte to the given extended register
+    ///
+    /// Only use this function if you really have to. For most use cases, the [`AgileExpanderInterface`] provides simpler ways of interacting with the device.
+    fn write_extended_byte(
+        &mut self,
+        register: ExtendedRegister,
+        data: u8,
+    ) -> Result<(), ExpanderError<E>> {
+        self.write(register as u8, &[data])
+    }
+
+    /// Reads one byte of the given extended register
+    ///
+    /// Only use this function if you really have to. For most use cases, the [`AgileExpanderInterface`] provides simpler ways of interacting with the device.
+    fn read_extended_byte(
+        &mut self,
+        register: ExtendedRegister,
+        buffer: &mut u8,
+    ) -> Result<(), ExpanderError<E>> {
+        self.read(register as u8, core::slice::from_mut(buffer))
+    }
+
+    /// Writes one halfword to the given extended register
+    ///
+    /// Only use this function if you really have to. For most use cases, the [`AgileExpanderInterface`] provides simpler ways of interacting with the device.
+    ///
+    /// # Register pairs
+    /// Please see [`ExtendedRegister`] for more information about the register pairs. The eight most significant bits are written to the provided register.
+    fn write_extended_halfword(
+        &mut self,
+        register: ExtendedRegister,
+        data: u16,
+    ) -> Result<(), ExpanderError<E>> {
+        self.write(register as u8, &data.to_be_bytes())
+    }
+
+    /// Reads one halfword of the given extended register
+    ///
+    /// Only use this function if you really have to. For most use cases, the [`AgileExpanderInterface`] provides simpler ways of interacting with the device.
+    ///
+    /// # Register pairs
+    /// Please see [`ExtendedRegister`] for more information about the register pairs. The eight most significant bits are read from the provided register.
+    fn read_extended_halfword(
+        &mut self,
+        register: ExtendedRegister,
+        buffer: &mut u16,
+    ) -> Result<(), ExpanderError<E>> {
+        let mut reg_val: [u8; 2] = [0x00; 2];
+
+        self.read(register as u8, &mut reg_val)?;
+
+        *buffer = u16::from_be_bytes(reg_val);
+
+        Ok(())
+    }
+}
+
+impl<I2C, E> StandardExpanderInterface<I2C, E> for Pcal9535a<I2C>
+where
+    E: Debug,
+    I2C: I2c<Error = E>,
+{
+}
+
+impl<I2C, E> SplitPins<I2C> for Pcal9535a<I2C>
+where
+    E: Debug,
+    I2C: I2c<Error = E>,
+{
+    type Pins<'a, Io>
+        = ExpanderPins<'a, I2C, Io>
+    where
+        Io: SyncExpander<I2C> + 'a;
+
+    fn split_pins<Io>(expander: &Io) -> Result<Self::Pins<'_, Io>, ExpanderError<E>>
+    where
+        Io: SyncExpander<I2C>,
+    {
+        ExpanderPins::new(expander)
+    }
+}
+
+impl<I2C, E> AgileExpanderInterface<I2C, E> for Pcal9535a<I2C>
+where
+    E: Debug,
+    I2C: I2c<Error = E>,
+{
+}
@@ -0,0 +1,92 @@
+//! Contains the interrupt sources which allow to use the cached expanders on boards without a connected interrupt pin.
+use core::convert::Infallible;
+
+use hal::digital::{ErrorType, InputPin};
+
+/// Interrupt pin replacement for boards where the interrupt output of the device is not connected.
+///
+/// The pin always reports a pending interrupt, so the cached expanders fetch the input registers from the device on every read of an input,
+/// while the output, polarity inversion and configuration registers stay cached.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct NoInterruptPin;
+
+impl ErrorType for NoInterruptPin {
+    type Error = Infallible;
+}
+
+impl InputPin for NoInterruptPin {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+}
+
+/// Interrupt pin replacement which lets the cached input registers go stale for a configurable time window.
+///
+/// The pin reports a pending interrupt once the given window has passed since it last did so, which makes the cached expanders fetch the input registers
+/// at most once per window. In between, the cached input values are returned without any bus traffic.
+///
+/// The time is provided by the user supplied `clock` function returning a monotonic tick count in an arbitrary unit, for example milliseconds since startup.
+/// The window is given in the same unit. Overflowing tick counts are handled, as long as the clock does not wrap around twice within one window.
+#[derive(Debug, Clone)]
+pub struct PollingInterrupt<CLK>
+where
+    CLK: FnMut() -> u64,
+{
+    clock: CLK,
+    window: u64,
+    last_fetch: Option<u64>,
+}
+
+impl<CLK> PollingInterrupt<CLK>
+where
+    CLK: FnMut() -> u64,
+{
+    /// Creates a new polling interrupt using the given clock and staleness window.
+    pub fn new(clock: CLK, window: u64) -> Self {
+        Self {
+            clock,
+            window,
+            last_fetch: None,
+        }
+    }
+
+    /// Forces the next read of an input register to fetch the value from the device.
+    pub fn expire(&mut self) {
+        self.last_fetch = None;
+    }
+}
+
+impl<CLK> ErrorType for PollingInterrupt<CLK>
+where
+    CLK: FnMut() -> u64,
+{
+    type Error = Infallible;
+}
+
+impl<CLK> InputPin for PollingInterrupt<CLK>
+where
+    CLK: FnMut() -> u64,
+{
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        self.is_low().map(|low| !low)
+    }
+
+    /// Returns `true` if the staleness window has passed, which restarts the window.
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        let now = (self.clock)();
+
+        let stale = self
+            .last_fetch
+            .map_or(true, |last| now.wrapping_sub(last) >= self.window);
+
+        if stale {
+            self.last_fetch = Some(now);
+        }
+
+        Ok(stale)
+    }
+}
@@ -0,0 +1,82 @@
+//! Contains the functions to detect the presence of expanders on the bus.
+use hal::i2c::{Error as _, ErrorKind, I2c, NoAcknowledgeSource};
+
+use super::{ExpanderError, Register};
+
+/// Result of probing an I2C address for an expander.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Presence {
+    /// No device acknowledged the address.
+    ///
+    /// Bus implementations which can not tell the source of a missing acknowledge ([`NoAcknowledgeSource::Unknown`]) are reported as absent as well.
+    Absent,
+    /// A device answered like a PCA9535 family expander. Contains the value of the configuration register pair, read starting at [`Register::ConfigurationPort0`].
+    Expander { configuration: u16 },
+    /// A device acknowledged the address, but rejected the command byte of the configuration registers or does not implement the register pairs of the PCA9535 family.
+    Unrecognized,
+}
+
+impl Presence {
+    /// Returns `true` if a PCA9535 family expander answered on the address.
+    pub fn is_expander(&self) -> bool {
+        matches!(self, Presence::Expander { .. })
+    }
+}
+
+/// Probes the given 7-bit I2C address for a PCA9535 family expander.
+///
+/// The configuration register pair is read starting with each of its two registers. An expander acknowledges both transactions and returns the same register values
+/// in swapped order, as the register pointer toggles between the members of the pair. Devices behaving differently are reported as [`Presence::Unrecognized`].
+///
+/// The address is not checked against the address range of any chip variant, so all 7-bit addresses can be probed. No register is written by this function.
+///
+/// # Errors
+/// Bus errors other than a missing acknowledge are returned as [`ExpanderError::WriteReadError`].
+pub fn probe<I2C>(i2c: &mut I2C, address: u8) -> Result<Presence, ExpanderError<I2C::Error>>
+where
+    I2C: I2c,
+{
+    let mut pair_0: [u8; 2] = [0x00; 2];
+
+    if let Err(err) = i2c.write_read(address, &[Register::ConfigurationPort0 as u8], &mut pair_0) {
+        return match err.kind() {
+            ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data) => Ok(Presence::Unrecognized),
+            ErrorKind::NoAcknowledge(_) => Ok(Presence::Absent),
+            _ => Err(ExpanderError::WriteReadError(err)),
+        };
+    }
+
+    let mut pair_1: [u8; 2] = [0x00; 2];
+
+    if let Err(err) = i2c.write_read(address, &[Register::ConfigurationPort1 as u8], &mut pair_1) {
+        return match err.kind() {
+            ErrorKind::NoAcknowledge(_) => Ok(Presence::Unrecognized),
+            _ => Err(ExpanderError::WriteReadError(err)),
+        };
+    }
+
+    if pair_1 != [pair_0[1], pair_0[0]] {
+        return Ok(Presence::Unrecognized);
+    }
+
+    Ok(Presence::Expander {
+        configuration: u16::from_be_bytes(pair_0),
+    })
+}
+
+/// Probes all addresses of the range `32-39` using [`probe()`]. The returned presences are ordered by address.
+///
+/// # Errors
+/// Bus errors other than a missing acknowledge are returned as [`ExpanderError::WriteReadError`].
+pub fn scan<I2C>(i2c: &mut I2C) -> Result<[Presence; 8], ExpanderError<I2C::Error>>
+where
+    I2C: I2c,
+{
+    let mut presences = [Presence::Absent; 8];
+
+    for (address, presence) in (32..=39).zip(presences.iter_mut()) {
+        *presence = probe(i2c, address)?;
+    }
+
+    Ok(presences)
+}
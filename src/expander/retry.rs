@@ -0,0 +1,106 @@
+//! Contains the policy to retry failed bus transactions.
+use hal::delay::DelayNs;
+use hal::i2c::{Error as _, ErrorKind, I2c};
+
+/// Delay which does not wait at all, used by a [`RetryPolicy`] retrying failed transactions right away.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct NoDelay;
+
+impl DelayNs for NoDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+/// Policy of [`crate::Pca9535Immediate`] and [`crate::Pca9535Cached`] on retrying failed bus transactions.
+///
+/// A transaction is attempted up to the given maximum number of times, as long as the [`ErrorKind`] of the failure is considered retryable.
+/// By default, missing acknowledges, bus errors and lost arbitrations are retried (see [`RetryPolicy::is_transient()`]).
+/// Only the error of the last attempt is returned.
+///
+/// The default policy attempts each transaction once and therefore never retries.
+/// ```no_run
+/// use rppal::hal::Delay;
+/// use rppal::i2c::I2c;
+/// use pca9535::{Pca9535Immediate, RetryPolicy};
+///
+/// let i2c = I2c::new().unwrap();
+///
+/// let expander = Pca9535Immediate::new(i2c, 32)
+///     .with_retry_policy(RetryPolicy::new(3).with_delay(Delay::new(), 100));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryPolicy<D = NoDelay> {
+    max_attempts: u8,
+    delay: D,
+    delay_us: u32,
+    retryable: fn(ErrorKind) -> bool,
+}
+
+impl RetryPolicy {
+    /// Creates a policy attempting each transaction up to `max_attempts` times without waiting in between. A value of `0` is treated as `1`.
+    pub fn new(max_attempts: u8) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            delay: NoDelay,
+            delay_us: 0,
+            retryable: Self::is_transient,
+        }
+    }
+
+    /// Returns `true` for error kinds which are typically caused by transient disturbances of the bus: missing acknowledges, bus errors and lost arbitrations.
+    pub fn is_transient(kind: ErrorKind) -> bool {
+        matches!(
+            kind,
+            ErrorKind::NoAcknowledge(_) | ErrorKind::Bus | ErrorKind::ArbitrationLoss
+        )
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl<D> RetryPolicy<D>
+where
+    D: DelayNs,
+{
+    /// Waits for `delay_us` microseconds using the given delay before each retry.
+    pub fn with_delay<D2: DelayNs>(self, delay: D2, delay_us: u32) -> RetryPolicy<D2> {
+        RetryPolicy {
+            max_attempts: self.max_attempts,
+            delay,
+            delay_us,
+            retryable: self.retryable,
+        }
+    }
+
+    /// Sets the function deciding whether a failed transaction with the given [`ErrorKind`] is retried.
+    pub fn retry_on(self, retryable: fn(ErrorKind) -> bool) -> Self {
+        Self { retryable, ..self }
+    }
+
+    /// Returns the maximum number of attempts of each transaction.
+    pub fn max_attempts(&self) -> u8 {
+        self.max_attempts
+    }
+
+    /// Runs the given transaction on the bus until it succeeds, fails with an error which is not retryable or the maximum number of attempts is reached.
+    pub(crate) fn run<I2C: I2c, T>(
+        &mut self,
+        i2c: &mut I2C,
+        mut transaction: impl FnMut(&mut I2C) -> Result<T, I2C::Error>,
+    ) -> Result<T, I2C::Error> {
+        let mut attempt = 1;
+
+        loop {
+            match transaction(i2c) {
+                Err(err) if attempt < self.max_attempts && (self.retryable)(err.kind()) => {
+                    attempt += 1;
+                    self.delay.delay_us(self.delay_us);
+                }
+                result => return result,
+            }
+        }
+    }
+}
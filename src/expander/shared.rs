@@ -0,0 +1,190 @@
+//! Contains the implementation to share one interrupt line between multiple cached expanders.
+use core::fmt::Debug;
+use core::marker::PhantomData;
+
+use hal::delay::DelayNs;
+use hal::digital::{Error as _, ErrorType, InputPin};
+use hal::i2c::I2c;
+
+use crate::chip::Chip;
+use crate::mutex::{ExpanderMutex, LockError};
+
+use super::cached::{InputChanges, Pca9535Cached};
+use super::ExpanderError;
+
+/// A cached expander using the pin of a [`SharedInterrupt`].
+type SharedCached<'a, I2C, IP, M, C, RP, RD> =
+    Pca9535Cached<I2C, SharedInterruptPin<'a, IP, M>, C, RP, RD>;
+
+/// An interrupt line shared by multiple cached expanders.
+///
+/// Boards often wire-OR the open-drain interrupt outputs of several expanders onto a single input pin. This type takes ownership of that pin and hands out
+/// [`SharedInterruptPin`]s, which can be passed to the constructors of all the [`Pca9535Cached`] expanders connected to the line.
+///
+/// While any of the devices asserts the interrupt, each expander fetches its input registers on the next read, as it can not tell whether the interrupt
+/// originates from its own device. Once the line is released again, all expanders return their cached inputs. [`SharedInterrupt::dispatch()`] reads the devices
+/// one after the other until the line is released, so only the devices in front of the last changed one are accessed.
+/// ```no_run
+/// use std::sync::Mutex;
+/// use embedded_hal_bus::i2c::RefCellDevice;
+/// use core::cell::RefCell;
+/// use rppal::gpio::Gpio;
+/// use rppal::i2c::I2c;
+/// use pca9535::{Pca9535Cached, SharedInterrupt};
+///
+/// let gpio = Gpio::new().unwrap();
+/// let i2c = RefCell::new(I2c::new().unwrap());
+///
+/// let interrupt: SharedInterrupt<_, Mutex<_>> = SharedInterrupt::new(gpio.get(0).unwrap().into_input());
+///
+/// let mut expander_0 = Pca9535Cached::new(RefCellDevice::new(&i2c), 32, interrupt.pin(), true).unwrap();
+/// let mut expander_1 = Pca9535Cached::new(RefCellDevice::new(&i2c), 33, interrupt.pin(), true).unwrap();
+///
+/// let [changes_0, changes_1] = interrupt.dispatch([&mut expander_0, &mut expander_1]).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct SharedInterrupt<IP, M>
+where
+    IP: InputPin + Send,
+    M: ExpanderMutex<IP>,
+{
+    pin_mutex: M,
+    phantom_data: PhantomData<IP>,
+}
+
+impl<IP, M> SharedInterrupt<IP, M>
+where
+    IP: InputPin + Send,
+    M: ExpanderMutex<IP>,
+{
+    /// Creates a new shared interrupt line out of the input pin connected to it.
+    pub fn new(interrupt_pin: IP) -> Self {
+        Self {
+            pin_mutex: M::new(interrupt_pin),
+            phantom_data: PhantomData,
+        }
+    }
+
+    /// Returns a new handle of the shared interrupt pin, which can be passed to a cached expander.
+    pub fn pin(&self) -> SharedInterruptPin<'_, IP, M> {
+        SharedInterruptPin { shared: self }
+    }
+
+    /// Returns `true` if any of the devices connected to the line asserts its interrupt.
+    ///
+    /// # Errors
+    /// Returns [`SharedInterruptError::Pin`] containing the [`hal::digital::ErrorKind`] of a failing pin, or [`SharedInterruptError::Lock`] if the pin could
+    /// not be locked.
+    pub fn is_active(&self) -> Result<bool, SharedInterruptError> {
+        self.pin_mutex
+            .lock(|pin| {
+                pin.is_low()
+                    .map_err(|err| SharedInterruptError::Pin(err.kind()))
+            })
+            .map_err(SharedInterruptError::Lock)?
+    }
+
+    /// Determines which of the given expanders have changed inputs and updates their caches.
+    ///
+    /// The expanders are checked in the given order using [`Pca9535Cached::input_changes()`] as long as the interrupt line is asserted. Once the line is released,
+    /// the remaining expanders have no pending changes and are not accessed. The returned changes correspond to the given expanders.
+    ///
+    /// All given expanders need to use a pin of this shared interrupt line.
+    pub fn dispatch<'a, I2C, E, C, RP, RD, const N: usize>(
+        &'a self,
+        expanders: [&mut SharedCached<'a, I2C, IP, M, C, RP, RD>; N],
+    ) -> Result<[InputChanges; N], ExpanderError<E>>
+    where
+        E: Debug,
+        I2C: I2c<Error = E>,
+        C: Chip,
+        RD: DelayNs,
+    {
+        let mut changes = [InputChanges::default(); N];
+
+        for (expander, changes) in expanders.into_iter().zip(changes.iter_mut()) {
+            let active = self.is_active().map_err(|err| match err {
+                SharedInterruptError::Pin(kind) => ExpanderError::InterruptPinError(kind),
+                SharedInterruptError::Lock(err) => ExpanderError::LockError(err),
+            })?;
+
+            if !active {
+                break;
+            }
+
+            *changes = expander.input_changes()?;
+        }
+
+        Ok(changes)
+    }
+}
+
+/// Handle of a [`SharedInterrupt`], which is used as the interrupt pin of a cached expander.
+#[derive(Debug)]
+pub struct SharedInterruptPin<'a, IP, M>
+where
+    IP: InputPin + Send,
+    M: ExpanderMutex<IP>,
+{
+    shared: &'a SharedInterrupt<IP, M>,
+}
+
+impl<IP, M> ErrorType for SharedInterruptPin<'_, IP, M>
+where
+    IP: InputPin + Send,
+    M: ExpanderMutex<IP>,
+{
+    type Error = SharedInterruptError;
+}
+
+impl<IP, M> InputPin for SharedInterruptPin<'_, IP, M>
+where
+    IP: InputPin + Send,
+    M: ExpanderMutex<IP>,
+{
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        self.shared
+            .pin_mutex
+            .lock(|pin| {
+                pin.is_high()
+                    .map_err(|err| SharedInterruptError::Pin(err.kind()))
+            })
+            .map_err(SharedInterruptError::Lock)?
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        self.shared.is_active()
+    }
+}
+
+/// Error of a [`SharedInterrupt`] and its [`SharedInterruptPin`]s
+///
+/// As a [`hal::digital::Error`], a failed lock is reported as [`hal::digital::ErrorKind::Other`]. This is also the kind a [`Pca9535Cached`] reports as
+/// [`ExpanderError::InterruptPinError`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SharedInterruptError {
+    /// The shared interrupt pin failed
+    Pin(hal::digital::ErrorKind),
+    /// The shared interrupt pin could not be locked
+    Lock(LockError),
+}
+
+impl hal::digital::Error for SharedInterruptError {
+    fn kind(&self) -> hal::digital::ErrorKind {
+        match self {
+            Self::Pin(kind) => *kind,
+            Self::Lock(_) => hal::digital::ErrorKind::Other,
+        }
+    }
+}
+
+impl core::fmt::Display for SharedInterruptError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Pin(kind) => write!(f, "the shared interrupt pin failed: {}", kind),
+            Self::Lock(err) => write!(f, "locking the shared interrupt pin failed: {}", err),
+        }
+    }
+}
+
+impl core::error::Error for SharedInterruptError {}
@@ -3,7 +3,7 @@ use core::fmt::Debug;
 
 use hal::i2c::I2c;
 
-use super::{Expander, ExpanderError, GPIOBank, Register};
+use super::{check_pin, Expander, ExpanderError, GPIOBank, Register};
 
 /// Standard expander interface not using [`hal`].
 ///
@@ -15,7 +15,7 @@ where
     I2C: I2c<Error = E>,
 {
     fn pin_set_high(&mut self, bank: GPIOBank, pin: u8) -> Result<(), ExpanderError<E>> {
-        assert!(pin < 8);
+        check_pin(pin)?;
 
         let register = match bank {
             GPIOBank::Bank0 => Register::OutputPort0,
@@ -31,10 +31,10 @@ where
 
     /// Drives given pin low.
     ///
-    /// # Panics
-    /// The function will panic if the provided pin is not in the allowed range of 0-7
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
     fn pin_set_low(&mut self, bank: GPIOBank, pin: u8) -> Result<(), ExpanderError<E>> {
-        assert!(pin < 8);
+        check_pin(pin)?;
 
         let register = match bank {
             GPIOBank::Bank0 => Register::OutputPort0,
@@ -48,15 +48,68 @@ where
         self.write_byte(register, reg_val & !(0x01 << pin))
     }
 
+    /// Toggles the output state of the given pin.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
+    fn pin_toggle(&mut self, bank: GPIOBank, pin: u8) -> Result<(), ExpanderError<E>> {
+        check_pin(pin)?;
+
+        let register = match bank {
+            GPIOBank::Bank0 => Register::OutputPort0,
+            GPIOBank::Bank1 => Register::OutputPort1,
+        };
+
+        let mut reg_val: u8 = 0x00;
+
+        self.read_byte(register, &mut reg_val)?;
+
+        self.write_byte(register, reg_val ^ (0x01 << pin))
+    }
+
+    /// Checks if the given pin is set to drive `high`.
+    ///
+    /// Unlike [`StandardExpanderInterface::pin_is_high()`], this function checks the value inside the output register of the device and not the actual input state of the pin.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
+    fn pin_is_set_high(&mut self, bank: GPIOBank, pin: u8) -> Result<bool, ExpanderError<E>> {
+        check_pin(pin)?;
+
+        let register = match bank {
+            GPIOBank::Bank0 => Register::OutputPort0,
+            GPIOBank::Bank1 => Register::OutputPort1,
+        };
+
+        let mut reg_val: u8 = 0x00;
+
+        self.read_byte(register, &mut reg_val)?;
+
+        match (reg_val >> pin) & 1 {
+            1 => Ok(true),
+            _ => Ok(false),
+        }
+    }
+
+    /// Checks if the given pin is set to drive `low`.
+    ///
+    /// Unlike [`StandardExpanderInterface::pin_is_low()`], this function checks the value inside the output register of the device and not the actual input state of the pin.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
+    fn pin_is_set_low(&mut self, bank: GPIOBank, pin: u8) -> Result<bool, ExpanderError<E>> {
+        Ok(!self.pin_is_set_high(bank, pin)?)
+    }
+
     /// Checks if the input state of the given pin is `high`. This function works with pins configured as inputs as well as outputs.
     ///
     /// The function result does not necessarily represent the logic level of the applied voltage at the given pin but the value inside the input register of the device.
     /// Which is `1` or `0` Depending on the current polarity inversion configuration of the pin.
     ///
-    /// # Panics
-    /// The function will panic if the provided pin is not in the allowed range of 0-7
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
     fn pin_is_high(&mut self, bank: GPIOBank, pin: u8) -> Result<bool, ExpanderError<E>> {
-        assert!(pin < 8);
+        check_pin(pin)?;
 
         let register = match bank {
             GPIOBank::Bank0 => Register::InputPort0,
@@ -78,10 +131,10 @@ where
     /// The function result does not necessarily represent the logic level of the applied voltage at the given pin but the value inside the input register of the device.
     /// Which is `1` or `0` Depending on the current polarity inversion configuration of the pin.
     ///
-    /// # Panics
-    /// The function will panic if the provided pin is not in the allowed range of 0-7
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
     fn pin_is_low(&mut self, bank: GPIOBank, pin: u8) -> Result<bool, ExpanderError<E>> {
-        assert!(pin < 8);
+        check_pin(pin)?;
 
         let register = match bank {
             GPIOBank::Bank0 => Register::InputPort0,
@@ -100,10 +153,10 @@ where
 
     /// Configures given pin as input.
     ///
-    /// # Panics
-    /// The function will panic if the provided pin is not in the allowed range of 0-7
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
     fn pin_into_input(&mut self, bank: GPIOBank, pin: u8) -> Result<(), ExpanderError<E>> {
-        assert!(pin < 8);
+        check_pin(pin)?;
 
         let register = match bank {
             GPIOBank::Bank0 => Register::ConfigurationPort0,
@@ -119,10 +172,10 @@ where
 
     /// Configures the given pin as output.
     ///
-    /// # Panics
-    /// The function will panic if the provided pin is not in the allowed range of 0-7
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
     fn pin_into_output(&mut self, bank: GPIOBank, pin: u8) -> Result<(), ExpanderError<E>> {
-        assert!(pin < 8);
+        check_pin(pin)?;
 
         let register = match bank {
             GPIOBank::Bank0 => Register::ConfigurationPort0,
@@ -140,10 +193,10 @@ where
     ///
     /// A logic high voltage applied at this input pin results in a `0` written to the devices input register and thus being registered as `low` by the driver.
     ///
-    /// # Panics
-    /// The function will panic if the provided pin is not in the allowed range of 0-7
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
     fn pin_inverse_polarity(&mut self, bank: GPIOBank, pin: u8) -> Result<(), ExpanderError<E>> {
-        assert!(pin < 8);
+        check_pin(pin)?;
 
         let register = match bank {
             GPIOBank::Bank0 => Register::PolarityInversionPort0,
@@ -161,10 +214,10 @@ where
     ///
     /// A logic high voltage applied at an input pin results in a `1` written to the device's input register, thus being registered as `high` by the driver.
     ///
-    /// # Panics
-    /// The function will panic if the provided pin is not in the allowed range of 0-7
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
     fn pin_normal_polarity(&mut self, bank: GPIOBank, pin: u8) -> Result<(), ExpanderError<E>> {
-        assert!(pin < 8);
+        check_pin(pin)?;
 
         let register = match bank {
             GPIOBank::Bank0 => Register::PolarityInversionPort0,
@@ -178,6 +231,139 @@ where
         self.write_byte(register, reg_val & !(0x01 << pin))
     }
 
+    /// Drives all pins of the given bank according to the given value, where bit `n` holds the state of pin `n`.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::UnsupportedRegister`] if the device does not provide the given bank, which is the case for [`GPIOBank::Bank1`] on 8-bit expanders.
+    fn port_write(&mut self, bank: GPIOBank, value: u8) -> Result<(), ExpanderError<E>> {
+        let register = match bank {
+            GPIOBank::Bank0 => Register::OutputPort0,
+            GPIOBank::Bank1 => Register::OutputPort1,
+        };
+
+        self.write_byte(register, value)
+    }
+
+    /// Reads the input states of all pins of the given bank, where bit `n` holds the state of pin `n`.
+    ///
+    /// Like [`StandardExpanderInterface::pin_is_high()`], the result represents the value inside the input register of the device.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::UnsupportedRegister`] if the device does not provide the given bank, which is the case for [`GPIOBank::Bank1`] on 8-bit expanders.
+    fn port_read(&mut self, bank: GPIOBank) -> Result<u8, ExpanderError<E>> {
+        let register = match bank {
+            GPIOBank::Bank0 => Register::InputPort0,
+            GPIOBank::Bank1 => Register::InputPort1,
+        };
+
+        let mut reg_val: u8 = 0x00;
+
+        self.read_byte(register, &mut reg_val)?;
+
+        Ok(reg_val)
+    }
+
+    /// Drives all pins of both banks according to the given value using a single register pair write.
+    ///
+    /// # Pin masks
+    /// Bit `n` of the value represents pin `n` of [`GPIOBank::Bank0`] and bit `8 + n` represents pin `n` of [`GPIOBank::Bank1`].
+    ///
+    /// # Errors
+    /// On 8-bit expanders, returns [`ExpanderError::UnsupportedRegister`] without changing any output if the value contains set bits of [`GPIOBank::Bank1`].
+    fn write_outputs(&mut self, value: u16) -> Result<(), ExpanderError<E>> {
+        self.write_halfword(Register::OutputPort0, value.swap_bytes())
+    }
+
+    /// Reads the input states of all pins of both banks using a single register pair read.
+    ///
+    /// # Pin masks
+    /// Bit `n` of the result represents pin `n` of [`GPIOBank::Bank0`] and bit `8 + n` represents pin `n` of [`GPIOBank::Bank1`].
+    ///
+    /// # Errors
+    /// Only fails if the device access fails. On 8-bit expanders, the bits of [`GPIOBank::Bank1`] read as `0`.
+    fn read_inputs(&mut self) -> Result<u16, ExpanderError<E>> {
+        let mut reg_val: u16 = 0x0000;
+
+        self.read_halfword(Register::InputPort0, &mut reg_val)?;
+
+        Ok(reg_val.swap_bytes())
+    }
+
+    /// Drives all pins set in the given mask high, leaving the other pins untouched.
+    ///
+    /// # Pin masks
+    /// Bit `n` of the mask represents pin `n` of [`GPIOBank::Bank0`] and bit `8 + n` represents pin `n` of [`GPIOBank::Bank1`].
+    ///
+    /// # Errors
+    /// On 8-bit expanders, returns [`ExpanderError::UnsupportedRegister`] without changing any output if the mask contains bits of [`GPIOBank::Bank1`].
+    fn pins_set_high(&mut self, mask: u16) -> Result<(), ExpanderError<E>> {
+        let mut reg_val: u16 = 0x0000;
+
+        self.read_halfword(Register::OutputPort0, &mut reg_val)?;
+
+        self.write_halfword(Register::OutputPort0, reg_val | mask.swap_bytes())
+    }
+
+    /// Drives all pins set in the given mask low, leaving the other pins untouched.
+    ///
+    /// # Pin masks
+    /// Bit `n` of the mask represents pin `n` of [`GPIOBank::Bank0`] and bit `8 + n` represents pin `n` of [`GPIOBank::Bank1`].
+    ///
+    /// # Errors
+    /// Only fails if the device access fails. On 8-bit expanders, bits of [`GPIOBank::Bank1`] in the mask are ignored.
+    fn pins_set_low(&mut self, mask: u16) -> Result<(), ExpanderError<E>> {
+        let mut reg_val: u16 = 0x0000;
+
+        self.read_halfword(Register::OutputPort0, &mut reg_val)?;
+
+        self.write_halfword(Register::OutputPort0, reg_val & !mask.swap_bytes())
+    }
+
+    /// Toggles the output state of all pins set in the given mask, leaving the other pins untouched.
+    ///
+    /// # Pin masks
+    /// Bit `n` of the mask represents pin `n` of [`GPIOBank::Bank0`] and bit `8 + n` represents pin `n` of [`GPIOBank::Bank1`].
+    ///
+    /// # Errors
+    /// On 8-bit expanders, returns [`ExpanderError::UnsupportedRegister`] without changing any output if the mask contains bits of [`GPIOBank::Bank1`].
+    fn pins_toggle(&mut self, mask: u16) -> Result<(), ExpanderError<E>> {
+        let mut reg_val: u16 = 0x0000;
+
+        self.read_halfword(Register::OutputPort0, &mut reg_val)?;
+
+        self.write_halfword(Register::OutputPort0, reg_val ^ mask.swap_bytes())
+    }
+
+    /// Configures all pins set in the given mask as inputs, leaving the other pins untouched.
+    ///
+    /// # Pin masks
+    /// Bit `n` of the mask represents pin `n` of [`GPIOBank::Bank0`] and bit `8 + n` represents pin `n` of [`GPIOBank::Bank1`].
+    ///
+    /// # Errors
+    /// On 8-bit expanders, returns [`ExpanderError::UnsupportedRegister`] without changing any configuration if the mask contains bits of [`GPIOBank::Bank1`].
+    fn pins_into_input(&mut self, mask: u16) -> Result<(), ExpanderError<E>> {
+        let mut reg_val: u16 = 0x0000;
+
+        self.read_halfword(Register::ConfigurationPort0, &mut reg_val)?;
+
+        self.write_halfword(Register::ConfigurationPort0, reg_val | mask.swap_bytes())
+    }
+
+    /// Configures all pins set in the given mask as outputs, leaving the other pins untouched.
+    ///
+    /// # Pin masks
+    /// Bit `n` of the mask represents pin `n` of [`GPIOBank::Bank0`] and bit `8 + n` represents pin `n` of [`GPIOBank::Bank1`].
+    ///
+    /// # Errors
+    /// Only fails if the device access fails. On 8-bit expanders, bits of [`GPIOBank::Bank1`] in the mask are ignored.
+    fn pins_into_output(&mut self, mask: u16) -> Result<(), ExpanderError<E>> {
+        let mut reg_val: u16 = 0x0000;
+
+        self.read_halfword(Register::ConfigurationPort0, &mut reg_val)?;
+
+        self.write_halfword(Register::ConfigurationPort0, reg_val & !mask.swap_bytes())
+    }
+
     /// Sets the input polarity of all pins to inverted.
     ///
     /// A logic high voltage applied at an input pin results in a `0` written to the device's input register, thus being registered as `low` by the driver.
@@ -12,10 +12,67 @@ On initialization, all pins are configured as high-impedance inputs. The PCA9535
 ### I2C
 The device uses 7Bit addressing and allows the hardware configuration of the first 3 address bits, allowing for up to 8 expanders on the same bus.
 
+Whether an expander answers on a given address can be checked using [`probe()`] before using the device. It distinguishes a missing acknowledge from devices
+which answer, but do not behave like a PCA9535 family expander, and returns the configuration of a detected expander. [`scan()`] probes all addresses of the range `32-39`.
+```no_run
+use rppal::i2c::I2c;
+use pca9535::Presence;
+
+let mut i2c = I2c::new().unwrap();
+
+match pca9535::probe(&mut i2c, 32).unwrap() {
+    Presence::Expander { configuration } => println!("configuration: {configuration:#06x}"),
+    Presence::Absent => println!("no device connected"),
+    Presence::Unrecognized => println!("another device answered"),
+}
+```
+
+On disturbed buses, [`Pca9535Immediate`] and [`Pca9535Cached`] can retry failed transactions according to a [`RetryPolicy`], which defines the maximum number of attempts,
+an optional delay between them and the [`hal::i2c::ErrorKind`]s worth retrying.
+Where a write needs to be confirmed, for example when switching safety-relevant relays, `set_write_verification()` makes both expanders read back each written output
+and configuration register and report a differing value as [`ExpanderError::WriteVerifyMismatch`].
+
+### Chip variants
+The register compatible PCA9555, TCA9535, TCA9555 and PCA9539 are supported as well. The variant is selected using the marker types of the [`chip`] module,
+which determine the valid address range and the power-on register defaults of the device.
+```no_run
+use rppal::i2c::I2c;
+use pca9535::chip::Pca9539;
+use pca9535::Pca9535Immediate;
+
+let i2c = I2c::new().unwrap();
+
+let expander = Pca9535Immediate::with_chip(i2c, 116, Pca9539).unwrap();
+```
+The RESET input of the PCA9539 can be controlled by handing over the connected output pin using `with_reset_pin()`, which enables `reset()` to bring the device back into its power-on state.
+After the device lost its state unexpectedly, [`Pca9535Cached::restore()`] re-applies the last known register values from the cache instead.
+
+The 8-bit PCA9534, PCA9554 and TCA9554 only provide the pins of [`GPIOBank::Bank0`]. They are supported by the [`Pca9554Immediate`] expander, which implements the
+same traits as the 16-bit expanders and therefore works with the [`StandardExpanderInterface`], [`IoExpander`] and the hal pins.
+
 ## General info
 The library uses the blocking I2C embedded-hal traits. Each implementation of [`Expander`] owns the provided I2C instance.
 If multiple drivers/devices need access to the same I2C bus, sharing the bus using crates like [embedded-hal-bus](https://crates.io/crates/embedded-hal-bus) is recommended.
 
+### Multiple expanders
+A fully populated bus of eight PCA9535 family devices on the addresses `32-39` can be driven by the [`MultiExpander`], which owns the bus and detects the
+connected devices using [`MultiExpander::scan()`]. Its 128 pins are addressed using a flat pin index, which is also used to create hal pins using
+[`MultiExpander::input_pin()`] and [`MultiExpander::output_pin()`]. [`MultiExpander::device()`] returns a handle of a single device, which implements the
+[`StandardExpanderInterface`] and can be used to create the hal pins as well.
+
+### Async
+By enabling the "async" feature of this crate, async counterparts of the expander types and traits built on [embedded-hal-async](https://crates.io/crates/embedded-hal-async) become available.
+Those are `Pca9535ImmediateAsync` and `Pca9535CachedAsync` which implement the `AsyncExpander` and `AsyncStandardExpanderInterface` traits.
+They work just like their blocking counterparts, except that all register reads/writes and pin operations need to be `.await`ed.
+```ignore
+use pca9535::{AsyncStandardExpanderInterface, GPIOBank, Pca9535ImmediateAsync};
+
+let mut expander = Pca9535ImmediateAsync::new(i2c, 32); // i2c implements embedded_hal_async::i2c::I2c
+
+expander.pin_into_output(GPIOBank::Bank0, 3).await.unwrap();
+expander.pin_set_high(GPIOBank::Bank0, 3).await.unwrap();
+```
+
 # Usage
 This library can be used in multiple ways depending on the use case and needs.
 
@@ -36,7 +93,10 @@ let expander = Pca9535Immediate::new(i2c, address);
 ```
 ### Cached
 The cached expander interface [`Pca9535Cached`] stores the state of the device registers internally to reduce the i2c bus traffic as much as possible.
-It relies on the open drain interrupt pin of the device to detect any changes to the registers. Thus, the use of this hardware pin is mandatory for this interface.
+It relies on the open drain interrupt pin of the device to detect any changes to the registers. On boards where the interrupt pin is not connected,
+[`NoInterruptPin`] or [`PollingInterrupt`] can be used instead, which fetch the input registers on every read or at most once per staleness window, while the
+output, polarity inversion and configuration registers stay cached. If the interrupt outputs of multiple expanders are connected to the same pin,
+the [`SharedInterrupt`] hands out a pin handle for each of the expanders and determines which of the devices have changed inputs.
 ```no_run
 use rppal::i2c::I2c;
 use rppal::gpio::Gpio;
@@ -51,6 +111,46 @@ let address = 32;
 
 let expander = Pca9535Cached::new(i2c, address, expander_interrupt_pin, true); // create cached expander and initialize cache to defaults
 ```
+Besides reading single pins, the cached expander can report which input pins changed once the interrupt pin indicates a change using [`Pca9535Cached::input_changes()`].
+This allows to react on button presses and the like without polling every pin.
+```no_run
+# use rppal::i2c::I2c;
+# use rppal::gpio::Gpio;
+# use pca9535::Pca9535Cached;
+#
+# let gpio = Gpio::new().unwrap();
+# let expander_interrupt_pin = gpio.get(0).unwrap().into_input();
+# let i2c = I2c::new().unwrap();
+# let mut expander = Pca9535Cached::new(i2c, 32, expander_interrupt_pin, true).unwrap();
+#
+let changes = expander.input_changes().unwrap();
+
+if changes.falling & (1 << 3) != 0 {
+    // pin 3 of bank 0 went low
+}
+```
+If other bus masters or glitches might change the device registers, [`Pca9535Cached::verify_cache()`] reports the registers whose cached value diverged from the device
+and [`Pca9535Cached::resync()`] refreshes the cache. Using a [`VerifyPolicy`], the cache can also be verified automatically every given number of operations.
+
+By default, each write is transferred to the device immediately. With the [`WritePolicy::WriteBack`] policy, writes to the output and configuration registers only
+update the cache until [`Pca9535Cached::flush()`] transfers all changed register pairs at once, which suits frequent updates like LED animations.
+If the expander is wrapped in an [`IoExpander`], [`IoExpander::with_expander()`] gives access to these functions.
+### Agile I/O
+The pin-compatible PCAL9535A and PCAL9555A provide additional "Agile I/O" registers, like pull-up/down resistors, interrupt masks and an interrupt status register.
+The [`Pcal9535a`] expander issues an i2c bus transaction on each function call just like the immediate expander and gives access to those features using the [`AgileExpanderInterface`].
+```no_run
+use rppal::i2c::I2c;
+use pca9535::{AgileExpanderInterface, GPIOBank, Pcal9535a, Pull};
+
+let i2c = I2c::new().unwrap();
+let mut expander = Pcal9535a::new(i2c, 32);
+
+expander.pin_set_pull(GPIOBank::Bank0, 1, Pull::Up).unwrap();
+expander.pin_unmask_interrupt(GPIOBank::Bank0, 1).unwrap();
+
+let interrupt_sources = expander.interrupt_status().unwrap();
+```
+
 ## Usage types
 Once the operation type has been determined, there are two ways of interacting with the IO expander:
 
@@ -90,8 +190,11 @@ let mut expander = Pca9535Immediate::new(i2c, address); //Either Immediate or Ca
 let io_expander: IoExpander<_, _, Mutex<_>> = IoExpander::new(expander); // Wrapped expander in std environment using Mutex as ExpanderMutex
 ```
 Using this wrapper, the expander gets automatically wrapped into an [`ExpanderMutex`], which ensures exclusive access to the expander and makes it [`Sync`].
-Currently, ExpanderMutex is only implemented for the `std` environment. You can activate this implementation by enabling the "std" feature of this crate. For other architectures on bare metal, etc.
-The ExpanderMutex trait can be implemented on any type, which ensures exclusive access to the contained data. Once this is done, the expander can be wrapped inside an IoExpander as described previously
+ExpanderMutex is implemented for `std::sync::Mutex` and `std::sync::RwLock` in the `std` environment, which you can activate by enabling the "std" feature of this crate.
+The "parking_lot" feature adds an implementation for `parking_lot::Mutex`. For bare metal targets, the "critical-section" feature provides an implementation for
+`critical_section::Mutex<RefCell<_>>` and the "spin" feature one for `spin::Mutex`. Single-threaded applications can use a [`core::cell::RefCell`] without enabling any feature.
+If the lock can not be acquired, for example because another thread panicked while holding a `std::sync::Mutex`, the pins return [`ExpanderError::LockError`] instead of panicking.
+For other synchronization types, the ExpanderMutex trait can be implemented on any type, which ensures exclusive access to the contained data. Once this is done, the expander can be wrapped inside an IoExpander as described previously
 using the newly implemented ExpanderMutex trait.
 
 Now, it is possible to generate either [`ExpanderInputPin`] or [`ExpanderOutputPin`] and manipulate the IO expander through those pins.
@@ -120,25 +223,115 @@ expander_pin_0_2.set_high();
 let is_high = expander_pin_1_5.is_high();
 // and so on...
 ```
+
+Creating pins this way does not prevent multiple pin instances for the same device pin. To rule this out at compile time, [`IoExpander::split()`] hands out all sixteen pins exactly once.
+The pins can then be converted between [`ExpanderInputPin`] and [`ExpanderOutputPin`] using `into_output()` and `into_input()`, which reconfigure the device accordingly.
+```no_run
+use std::sync::Mutex;
+use rppal::i2c::I2c;
+use hal::digital::OutputPin;
+use pca9535::{IoExpander, Pca9535Immediate, PinState};
+
+let i2c = I2c::new().unwrap();
+let expander = Pca9535Immediate::new(i2c, 32);
+
+let mut io_expander: IoExpander<_, _, Mutex<_>> = IoExpander::new(expander);
+let pins = io_expander.split().unwrap();
+
+let mut expander_pin_0_2 = pins.io0_2.into_output(PinState::Low).unwrap();
+expander_pin_0_2.set_high().unwrap();
+```
+
+Pins which change their direction at runtime, like the data line of a bidirectional protocol, can be created as [`ExpanderFlexPin`]. It implements the input and output
+traits at once and returns [`ExpanderError::DirectionMismatch`] when used in the direction it is not configured in.
+
+The pins above borrow the [`IoExpander`], which ties them to its lifetime. To move pins into threads or store them next to other owned drivers, [`ArcInputPin`] and
+[`ArcOutputPin`] hold an `Arc` of the [`IoExpander`] instead. More generally, [`HandleInputPin`] and [`HandleOutputPin`] accept any handle dereferencing to the expander,
+like a `&'static` reference to a statically allocated [`IoExpander`].
+```no_run
+use std::sync::{Arc, Mutex};
+use rppal::i2c::I2c;
+use hal::digital::OutputPin;
+use pca9535::{ArcOutputPin, GPIOBank, IoExpander, Pca9535Immediate, PinState};
+
+let i2c = I2c::new().unwrap();
+let expander = Pca9535Immediate::new(i2c, 32);
+
+let io_expander: Arc<IoExpander<_, _, Mutex<_>>> = Arc::new(IoExpander::new(expander));
+let mut led = ArcOutputPin::new(io_expander.clone(), GPIOBank::Bank0, 2, PinState::Low).unwrap();
+
+std::thread::spawn(move || led.set_high().unwrap());
+```
 */
 #![forbid(unsafe_code)]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod chip;
 pub mod expander;
 pub mod mutex;
 pub mod pin;
-
+#[cfg(feature = "sim")]
+pub mod sim;
+
+pub use chip::Chip;
+pub use expander::agile::AgileExpander;
+pub use expander::agile::AgileExpanderInterface;
+#[cfg(feature = "async")]
+pub use expander::asynch::cached::Pca9535CachedAsync;
+#[cfg(feature = "async")]
+pub use expander::asynch::immediate::Pca9535ImmediateAsync;
+#[cfg(feature = "async")]
+pub use expander::asynch::standard::AsyncStandardExpanderInterface;
+#[cfg(feature = "async")]
+pub use expander::asynch::AsyncExpander;
+pub use expander::cached::CacheMismatches;
+pub use expander::cached::InputChanges;
 pub use expander::cached::Pca9535Cached;
+pub use expander::cached::VerifyPolicy;
+pub use expander::cached::WritePolicy;
 pub use expander::immediate::Pca9535Immediate;
 pub use expander::io::IoExpander;
+pub use expander::io::IoTransaction;
+pub use expander::io::SplitPins;
+pub use expander::multi::DeviceHandle;
+pub use expander::multi::MultiExpander;
+pub use expander::multi::MultiExpanderDevice;
+pub use expander::multi::MultiInputPin;
+pub use expander::multi::MultiOutputPin;
+pub use expander::pca9554::Pca9554Immediate;
+pub use expander::pcal9535a::Pcal9535a;
+pub use expander::polling::NoInterruptPin;
+pub use expander::polling::PollingInterrupt;
+pub use expander::probe::probe;
+pub use expander::probe::scan;
+pub use expander::probe::Presence;
+pub use expander::retry::NoDelay;
+pub use expander::retry::RetryPolicy;
+pub use expander::shared::SharedInterrupt;
+pub use expander::shared::SharedInterruptError;
+pub use expander::shared::SharedInterruptPin;
 pub use expander::standard::StandardExpanderInterface;
 pub use expander::Expander;
 pub use expander::ExpanderError;
+pub use expander::NoResetPin;
 pub use expander::SyncExpander;
 pub use hal::digital::PinState;
 pub use mutex::ExpanderMutex;
+pub use mutex::LockError;
+#[cfg(feature = "std")]
+pub use pin::ArcFlexPin;
+#[cfg(feature = "std")]
+pub use pin::ArcInputPin;
+#[cfg(feature = "std")]
+pub use pin::ArcOutputPin;
+pub use pin::ExpanderFlexPin;
 pub use pin::ExpanderInputPin;
 pub use pin::ExpanderOutputPin;
+pub use pin::ExpanderPins;
+pub use pin::ExpanderPins8;
+pub use pin::HandleFlexPin;
+pub use pin::HandleInputPin;
+pub use pin::HandleOutputPin;
 
 /// The data registers of the device
 ///
@@ -214,6 +407,18 @@ pub enum Register {
 }
 
 impl Register {
+    /// All registers, ordered by their command byte
+    const ALL: [Register; 8] = [
+        Self::InputPort0,
+        Self::InputPort1,
+        Self::OutputPort0,
+        Self::OutputPort1,
+        Self::PolarityInversionPort0,
+        Self::PolarityInversionPort1,
+        Self::ConfigurationPort0,
+        Self::ConfigurationPort1,
+    ];
+
     /// Return the other pair member of the given register
     fn get_neighbor(&self) -> Register {
         match self {
@@ -228,6 +433,17 @@ impl Register {
         }
     }
 
+    /// Returns the GPIO bank the register belongs to
+    fn bank(&self) -> GPIOBank {
+        match self {
+            Self::InputPort0
+            | Self::OutputPort0
+            | Self::PolarityInversionPort0
+            | Self::ConfigurationPort0 => GPIOBank::Bank0,
+            _ => GPIOBank::Bank1,
+        }
+    }
+
     /// Returns true if register is an input register
     fn is_input(&self) -> bool {
         matches!(self, Self::InputPort0 | Self::InputPort1)
@@ -240,6 +456,49 @@ impl Register {
             Self::PolarityInversionPort0 | Self::PolarityInversionPort1
         )
     }
+
+    /// Returns true if register is an output or configuration register
+    fn is_output_or_configuration(&self) -> bool {
+        matches!(
+            self,
+            Self::OutputPort0
+                | Self::OutputPort1
+                | Self::ConfigurationPort0
+                | Self::ConfigurationPort1
+        )
+    }
+}
+
+/// The extended "Agile I/O" registers of the PCAL9535A
+///
+/// The enum represents the command byte values used to access the corresponding registers. Like the standard [`Register`]s, the extended registers act as register pairs,
+/// except for the output port configuration register, which is a single register.
+///
+/// **Pairs**
+/// 1) OutputDriveStrength0A and OutputDriveStrength0B (pins 0-3 and 4-7 of bank 0)
+/// 2) OutputDriveStrength1A and OutputDriveStrength1B (pins 0-3 and 4-7 of bank 1)
+/// 3) InputLatch0 and InputLatch1
+/// 4) PullEnable0 and PullEnable1
+/// 5) PullSelection0 and PullSelection1
+/// 6) InterruptMask0 and InterruptMask1
+/// 7) InterruptStatus0 and InterruptStatus1
+#[derive(Debug, Copy, Clone)]
+pub enum ExtendedRegister {
+    OutputDriveStrength0A = 0x40,
+    OutputDriveStrength0B = 0x41,
+    OutputDriveStrength1A = 0x42,
+    OutputDriveStrength1B = 0x43,
+    InputLatch0 = 0x44,
+    InputLatch1 = 0x45,
+    PullEnable0 = 0x46,
+    PullEnable1 = 0x47,
+    PullSelection0 = 0x48,
+    PullSelection1 = 0x49,
+    InterruptMask0 = 0x4A,
+    InterruptMask1 = 0x4B,
+    InterruptStatus0 = 0x4C,
+    InterruptStatus1 = 0x4D,
+    OutputPortConfiguration = 0x4F,
 }
 
 /// The gpio banks of the device
@@ -249,9 +508,128 @@ pub enum GPIOBank {
     Bank1 = 1,
 }
 
+/// The I2C address of the device, as configured by its hardware address pins A0-A2
+///
+/// A [`HardwareAddress`] is always within the permittable address range of `32-39`, so creating an expander using `from_hardware_address()`, like
+/// [`Pca9535Immediate::from_hardware_address()`], or passing [`HardwareAddress::address()`] to the `new()` constructors of the expanders can never fail.
+/// This address range is shared by all supported chip variants except the [`chip::Pca9539`], which uses `116-119`, and the [`chip::Pca9534a`] and
+/// [`chip::Pca9554a`], which use `56-63`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct HardwareAddress(u8);
+
+impl HardwareAddress {
+    /// Creates the address from the logic levels applied to the hardware address pins, where `true` represents a connection to VDD.
+    pub const fn new(a2: bool, a1: bool, a0: bool) -> Self {
+        Self((a2 as u8) << 2 | (a1 as u8) << 1 | a0 as u8)
+    }
+
+    /// Creates the address from a 7-bit I2C address. Returns `None` if the given address is outside the permittable range of `32-39`.
+    pub const fn from_address(address: u8) -> Option<Self> {
+        if address > 31 && address < 40 {
+            Some(Self(address - 32))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the 7-bit I2C address of the device.
+    pub const fn address(&self) -> u8 {
+        32 + self.0
+    }
+}
+
+impl From<HardwareAddress> for u8 {
+    fn from(address: HardwareAddress) -> Self {
+        address.address()
+    }
+}
+
+/// Identifies one of the sixteen pins of the device
+///
+/// The pins are numbered from `0` to `15`, where index `n` represents pin `n` of [`GPIOBank::Bank0`] and index `8 + n` represents pin `n` of [`GPIOBank::Bank1`].
+/// This matches the bit positions used by all 16-bit pin masks of this crate. Creating hal pins using `from_pin_id()`, like [`ExpanderInputPin::from_pin_id()`],
+/// or changing pins in an [`IoTransaction`] using [`IoTransaction::set_pin()`] can therefore never fail due to an invalid pin.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PinId(u8);
+
+impl PinId {
+    /// Creates the pin id from a bank and pin number. Returns `None` if the given pin is not in the allowed range of 0-7.
+    pub const fn new(bank: GPIOBank, pin: u8) -> Option<Self> {
+        if pin < 8 {
+            Some(Self(bank as u8 * 8 + pin))
+        } else {
+            None
+        }
+    }
+
+    /// Creates the pin id from a pin index. Returns `None` if the given index is not in the allowed range of 0-15.
+    pub const fn from_index(index: u8) -> Option<Self> {
+        if index < 16 {
+            Some(Self(index))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the pin index in the range of 0-15.
+    pub const fn index(&self) -> u8 {
+        self.0
+    }
+
+    /// Returns the bank of the pin.
+    pub const fn bank(&self) -> GPIOBank {
+        if self.0 < 8 {
+            GPIOBank::Bank0
+        } else {
+            GPIOBank::Bank1
+        }
+    }
+
+    /// Returns the pin number within its bank in the range of 0-7.
+    pub const fn pin(&self) -> u8 {
+        self.0 % 8
+    }
+
+    /// Returns the 16-bit pin mask with only this pin set.
+    pub const fn mask(&self) -> u16 {
+        0x01 << self.0
+    }
+}
+
 /// The possible polarity states of inputs and outputs of the device
 #[derive(Debug, Copy, Clone)]
 pub enum Polarity {
     Normal = 0,
     Inverse = 1,
 }
+
+/// The direction a pin is configured in, as set in the configuration registers of the device
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Direction {
+    Input,
+    Output,
+}
+
+/// The output drive strength of a pin of the PCAL9535A, relative to the maximum drive strength
+#[derive(Debug, Copy, Clone)]
+pub enum DriveStrength {
+    Quarter = 0,
+    Half = 1,
+    ThreeQuarters = 2,
+    Full = 3,
+}
+
+/// The internal pull resistor configuration of a pin of the PCAL9535A
+#[derive(Debug, Copy, Clone)]
+pub enum Pull {
+    None,
+    Up,
+    Down,
+}
+
+/// The output stage configuration of a gpio bank of the PCAL9535A
+#[derive(Debug, Copy, Clone)]
+pub enum OutputMode {
+    PushPull = 0,
+    OpenDrain = 1,
+}
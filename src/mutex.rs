@@ -1,28 +1,137 @@
 //! Contains the ExpanderMutex Trait to use an Expander accross threads.
+use core::cell::RefCell;
 
 /// Each type that can implement this trait can be used as a synchronization type for the [`crate::IoExpander`], which in turn is utilized to generate the [`hal`] pins. Due to this trait, the pins are synced and can be used across threads.
 ///
-/// This trait can be implemented on all kinds of types, which ensure exclusive access to the contained data. For `std` environments, this trait is already implemented. It can be enabled by enabling the "std" feature of this library.
+/// This trait can be implemented on all kinds of types, which ensure exclusive access to the contained data. The following implementations are provided by this library:
+/// - `std::sync::Mutex` and `std::sync::RwLock`, enabled by the "std" feature. A lock poisoned by a panicking thread is reported as [`LockError::Poisoned`].
+/// - `parking_lot::Mutex`, enabled by the "parking_lot" feature
+/// - `critical_section::Mutex<RefCell<_>>` for bare-metal targets, enabled by the "critical-section" feature
+/// - `spin::Mutex`, enabled by the "spin" feature
+/// - [`RefCell`], which is not [`Sync`] and therefore restricts the pins to the thread which created them
+///
+/// The [`RefCell`] based implementations report nested locking, for example by using a pin within [`crate::IoExpander::with_expander()`], as [`LockError::WouldBlock`].
+///
+/// # Fallible locking
+/// If the lock can not be acquired, the implementation returns a [`LockError`] instead of calling the closure. The expanders and hal pins report it as
+/// [`crate::ExpanderError::LockError`]. Implementations using try-lock or timeout semantics return [`LockError::WouldBlock`] once they give up.
 pub trait ExpanderMutex<Ex>
 where
     Ex: Send,
 {
-    fn lock<R, C: FnOnce(&mut Ex) -> R>(&self, c: C) -> R;
+    fn lock<R, C: FnOnce(&mut Ex) -> R>(&self, c: C) -> Result<R, LockError>;
 
     fn new(ex: Ex) -> Self;
 }
 
+/// Reason why an [`ExpanderMutex`] could not be locked
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LockError {
+    /// Another thread panicked while holding the lock
+    Poisoned,
+    /// The lock is held elsewhere and could not be acquired without blocking or within the timeout of the implementation
+    WouldBlock,
+}
+
+impl core::fmt::Display for LockError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Poisoned => write!(f, "the lock is poisoned"),
+            Self::WouldBlock => write!(f, "the lock is held elsewhere"),
+        }
+    }
+}
+
+impl core::error::Error for LockError {}
+
 #[cfg(feature = "std")]
 impl<Ex> ExpanderMutex<Ex> for std::sync::Mutex<Ex>
 where
     Ex: Send,
 {
-    fn lock<R, C: FnOnce(&mut Ex) -> R>(&self, c: C) -> R {
-        let mut expander = self.lock().unwrap();
-        c(&mut expander)
+    fn lock<R, C: FnOnce(&mut Ex) -> R>(&self, c: C) -> Result<R, LockError> {
+        let mut expander = self.lock().map_err(|_| LockError::Poisoned)?;
+        Ok(c(&mut expander))
     }
 
     fn new(ex: Ex) -> Self {
         std::sync::Mutex::new(ex)
     }
 }
+
+#[cfg(feature = "std")]
+impl<Ex> ExpanderMutex<Ex> for std::sync::RwLock<Ex>
+where
+    Ex: Send,
+{
+    fn lock<R, C: FnOnce(&mut Ex) -> R>(&self, c: C) -> Result<R, LockError> {
+        let mut expander = self.write().map_err(|_| LockError::Poisoned)?;
+        Ok(c(&mut expander))
+    }
+
+    fn new(ex: Ex) -> Self {
+        std::sync::RwLock::new(ex)
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+impl<Ex> ExpanderMutex<Ex> for parking_lot::Mutex<Ex>
+where
+    Ex: Send,
+{
+    fn lock<R, C: FnOnce(&mut Ex) -> R>(&self, c: C) -> Result<R, LockError> {
+        Ok(c(&mut self.lock()))
+    }
+
+    fn new(ex: Ex) -> Self {
+        parking_lot::Mutex::new(ex)
+    }
+}
+
+impl<Ex> ExpanderMutex<Ex> for RefCell<Ex>
+where
+    Ex: Send,
+{
+    fn lock<R, C: FnOnce(&mut Ex) -> R>(&self, c: C) -> Result<R, LockError> {
+        let mut expander = self.try_borrow_mut().map_err(|_| LockError::WouldBlock)?;
+        Ok(c(&mut expander))
+    }
+
+    fn new(ex: Ex) -> Self {
+        RefCell::new(ex)
+    }
+}
+
+#[cfg(feature = "critical-section")]
+impl<Ex> ExpanderMutex<Ex> for critical_section::Mutex<RefCell<Ex>>
+where
+    Ex: Send,
+{
+    fn lock<R, C: FnOnce(&mut Ex) -> R>(&self, c: C) -> Result<R, LockError> {
+        critical_section::with(|cs| {
+            let mut expander = self
+                .borrow(cs)
+                .try_borrow_mut()
+                .map_err(|_| LockError::WouldBlock)?;
+            Ok(c(&mut expander))
+        })
+    }
+
+    fn new(ex: Ex) -> Self {
+        critical_section::Mutex::new(RefCell::new(ex))
+    }
+}
+
+#[cfg(feature = "spin")]
+impl<Ex> ExpanderMutex<Ex> for spin::Mutex<Ex>
+where
+    Ex: Send,
+{
+    fn lock<R, C: FnOnce(&mut Ex) -> R>(&self, c: C) -> Result<R, LockError> {
+        Ok(c(&mut self.lock()))
+    }
+
+    fn new(ex: Ex) -> Self {
+        spin::Mutex::new(ex)
+    }
+}
@@ -1,70 +1,291 @@
 //! Contains the implementation of the hal-pin usage inteface.
 use core::fmt::Debug;
 use core::marker::PhantomData;
+use core::ops::Deref;
+#[cfg(feature = "std")]
+use std::sync::Arc;
 
 use hal::digital::{ErrorType, PinState};
-use hal::digital::{InputPin, OutputPin};
+use hal::digital::{InputPin, OutputPin, StatefulOutputPin};
 use hal::i2c::I2c;
 
 use crate::ExpanderError;
 
-use super::expander::SyncExpander;
+use super::expander::{check_pin, SyncExpander};
+use super::Direction;
 use super::GPIOBank;
+use super::PinId;
 use super::Polarity;
 use super::Register;
 
 /// Single input device pin implementing [`InputPin`] trait.
 ///
 /// The [`ExpanderInputPin`] instance can be used with other pieces of software using [`hal`].
+pub type ExpanderInputPin<'a, I2C, Io> = HandleInputPin<I2C, &'a Io>;
+
+/// Single output device pin implementing [`OutputPin`] and [`StatefulOutputPin`] traits.
+///
+/// The [`ExpanderOutputPin`] instance can be used with other pieces of software using [`hal`].
+pub type ExpanderOutputPin<'a, I2C, Io> = HandleOutputPin<I2C, &'a Io>;
+
+/// Single input device pin holding an [`Arc`] of the expander.
+///
+/// As the pin does not borrow the expander, it is `'static` as long as the expander is, and can be moved into threads or stored in long-lived structs.
+#[cfg(feature = "std")]
+pub type ArcInputPin<I2C, Io> = HandleInputPin<I2C, Arc<Io>>;
+
+/// Single output device pin holding an [`Arc`] of the expander.
+///
+/// As the pin does not borrow the expander, it is `'static` as long as the expander is, and can be moved into threads or stored in long-lived structs.
+#[cfg(feature = "std")]
+pub type ArcOutputPin<I2C, Io> = HandleOutputPin<I2C, Arc<Io>>;
+
+/// Single device pin which can switch between input and output at runtime.
+///
+/// The [`ExpanderFlexPin`] instance can be used with other pieces of software using [`hal`].
+pub type ExpanderFlexPin<'a, I2C, Io> = HandleFlexPin<I2C, &'a Io>;
+
+/// Single device pin holding an [`Arc`] of the expander, which can switch between input and output at runtime.
+#[cfg(feature = "std")]
+pub type ArcFlexPin<I2C, Io> = HandleFlexPin<I2C, Arc<Io>>;
+
+/// Single input device pin implementing [`InputPin`] trait, which accesses the expander using a handle dereferencing to it.
+///
+/// The handle can be any type implementing [`Deref`] to a [`SyncExpander`], like a reference ([`ExpanderInputPin`]), an `Arc` ([`ArcInputPin`]) or a `'static`
+/// reference to a statically allocated expander.
+/// ```no_run
+/// use std::sync::{Arc, Mutex};
+/// use rppal::i2c::I2c;
+/// use hal::digital::InputPin;
+/// use pca9535::{ArcInputPin, GPIOBank, IoExpander, Pca9535Immediate};
+///
+/// let i2c = I2c::new().unwrap();
+/// let expander = Pca9535Immediate::new(i2c, 32);
+/// let io_expander: Arc<IoExpander<_, _, Mutex<_>>> = Arc::new(IoExpander::new(expander));
+///
+/// let mut button = ArcInputPin::new(io_expander.clone(), GPIOBank::Bank0, 3).unwrap();
+///
+/// std::thread::spawn(move || button.is_high().unwrap());
+/// ```
 #[derive(Debug)]
-pub struct ExpanderInputPin<'a, I2C, Io>
+pub struct HandleInputPin<I2C, H>
 where
     I2C: I2c,
-    Io: SyncExpander<I2C>,
+    H: Deref,
+    H::Target: SyncExpander<I2C>,
 {
-    expander: &'a Io,
+    expander: H,
     bank: GPIOBank,
     pin: u8,
-    phantom_data: PhantomData<I2C>,
+    phantom_data: PhantomData<fn() -> I2C>,
 }
 
-/// Single output device pin implementing [`OutputPin`] trait.
+/// Single output device pin implementing [`OutputPin`] and [`StatefulOutputPin`] traits, which accesses the expander using a handle dereferencing to it.
 ///
-/// The [`ExpanderInputPin`] instance can be used with other pieces of software using [`hal`].
+/// Please see [`HandleInputPin`] for more information on the handle.
 #[derive(Debug)]
-pub struct ExpanderOutputPin<'a, I2C, Io>
+pub struct HandleOutputPin<I2C, H>
 where
     I2C: I2c,
-    Io: SyncExpander<I2C>,
+    H: Deref,
+    H::Target: SyncExpander<I2C>,
+{
+    expander: H,
+    bank: GPIOBank,
+    pin: u8,
+    phantom_data: PhantomData<fn() -> I2C>,
+}
+
+/// Single device pin implementing the [`InputPin`], [`OutputPin`] and [`StatefulOutputPin`] traits, which can switch between input and output at runtime.
+///
+/// The pin keeps track of the direction it is configured in. Using it in the wrong direction, like calling [`OutputPin::set_high()`] on a pin configured as
+/// input, returns [`ExpanderError::DirectionMismatch`] without accessing the device. Please see [`HandleInputPin`] for more information on the handle.
+/// ```no_run
+/// use std::sync::Mutex;
+/// use rppal::i2c::I2c;
+/// use hal::digital::{InputPin, OutputPin};
+/// use pca9535::{Direction, ExpanderFlexPin, GPIOBank, IoExpander, Pca9535Immediate, PinState};
+///
+/// let i2c = I2c::new().unwrap();
+/// let expander = Pca9535Immediate::new(i2c, 32);
+/// let io_expander: IoExpander<_, _, Mutex<_>> = IoExpander::new(expander);
+///
+/// let mut data = ExpanderFlexPin::new(&io_expander, GPIOBank::Bank0, 5).unwrap();
+///
+/// data.set_as_output(PinState::Low).unwrap();
+/// data.set_high().unwrap();
+///
+/// data.set_as_input().unwrap();
+/// assert_eq!(data.direction(), Direction::Input);
+/// let is_high = data.is_high().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct HandleFlexPin<I2C, H>
+where
+    I2C: I2c,
+    H: Deref,
+    H::Target: SyncExpander<I2C>,
 {
-    expander: &'a Io,
+    expander: H,
     bank: GPIOBank,
     pin: u8,
-    phantom_data: PhantomData<I2C>,
+    direction: Direction,
+    phantom_data: PhantomData<fn() -> I2C>,
+}
+
+/// All sixteen pins of an expander as returned by [`crate::IoExpander::split()`].
+///
+/// The fields are named after the device's pins, so `io1_5` is pin 5 of [`GPIOBank::Bank1`].
+#[derive(Debug)]
+pub struct ExpanderPins<'a, I2C, Io>
+where
+    I2C: I2c,
+    Io: SyncExpander<I2C>,
+{
+    pub io0_0: ExpanderInputPin<'a, I2C, Io>,
+    pub io0_1: ExpanderInputPin<'a, I2C, Io>,
+    pub io0_2: ExpanderInputPin<'a, I2C, Io>,
+    pub io0_3: ExpanderInputPin<'a, I2C, Io>,
+    pub io0_4: ExpanderInputPin<'a, I2C, Io>,
+    pub io0_5: ExpanderInputPin<'a, I2C, Io>,
+    pub io0_6: ExpanderInputPin<'a, I2C, Io>,
+    pub io0_7: ExpanderInputPin<'a, I2C, Io>,
+    pub io1_0: ExpanderInputPin<'a, I2C, Io>,
+    pub io1_1: ExpanderInputPin<'a, I2C, Io>,
+    pub io1_2: ExpanderInputPin<'a, I2C, Io>,
+    pub io1_3: ExpanderInputPin<'a, I2C, Io>,
+    pub io1_4: ExpanderInputPin<'a, I2C, Io>,
+    pub io1_5: ExpanderInputPin<'a, I2C, Io>,
+    pub io1_6: ExpanderInputPin<'a, I2C, Io>,
+    pub io1_7: ExpanderInputPin<'a, I2C, Io>,
+}
+
+/// All eight pins of an 8-bit expander as returned by [`crate::IoExpander::split()`].
+///
+/// The fields are named after the device's pins, so `io0_5` is pin 5 of [`GPIOBank::Bank0`]. The pins of [`GPIOBank::Bank1`] do not exist on the device and
+/// are therefore not part of this struct.
+/// ```compile_fail
+/// use std::sync::Mutex;
+/// use rppal::i2c::I2c;
+/// use pca9535::{IoExpander, Pca9554Immediate};
+///
+/// let i2c = I2c::new().unwrap();
+/// let expander = Pca9554Immediate::new(i2c, 32);
+///
+/// let mut io_expander: IoExpander<_, _, Mutex<_>> = IoExpander::new(expander);
+/// let pins = io_expander.split().unwrap();
+///
+/// let missing = pins.io1_0;
+/// ```
+#[derive(Debug)]
+pub struct ExpanderPins8<'a, I2C, Io>
+where
+    I2C: I2c,
+    Io: SyncExpander<I2C>,
+{
+    pub io0_0: ExpanderInputPin<'a, I2C, Io>,
+    pub io0_1: ExpanderInputPin<'a, I2C, Io>,
+    pub io0_2: ExpanderInputPin<'a, I2C, Io>,
+    pub io0_3: ExpanderInputPin<'a, I2C, Io>,
+    pub io0_4: ExpanderInputPin<'a, I2C, Io>,
+    pub io0_5: ExpanderInputPin<'a, I2C, Io>,
+    pub io0_6: ExpanderInputPin<'a, I2C, Io>,
+    pub io0_7: ExpanderInputPin<'a, I2C, Io>,
+}
+
+impl<'a, I2C, Io> ExpanderPins<'a, I2C, Io>
+where
+    I2C: I2c,
+    Io: SyncExpander<I2C>,
+{
+    /// Configures all pins of both banks as inputs and creates their pin structs.
+    pub(crate) fn new(expander: &'a Io) -> Result<Self, ExpanderError<I2C::Error>> {
+        expander.write_halfword(Register::ConfigurationPort0, 0xFFFF)?;
+
+        let pin = |bank, pin| ExpanderInputPin::from_parts(expander, bank, pin);
+
+        Ok(Self {
+            io0_0: pin(GPIOBank::Bank0, 0),
+            io0_1: pin(GPIOBank::Bank0, 1),
+            io0_2: pin(GPIOBank::Bank0, 2),
+            io0_3: pin(GPIOBank::Bank0, 3),
+            io0_4: pin(GPIOBank::Bank0, 4),
+            io0_5: pin(GPIOBank::Bank0, 5),
+            io0_6: pin(GPIOBank::Bank0, 6),
+            io0_7: pin(GPIOBank::Bank0, 7),
+            io1_0: pin(GPIOBank::Bank1, 0),
+            io1_1: pin(GPIOBank::Bank1, 1),
+            io1_2: pin(GPIOBank::Bank1, 2),
+            io1_3: pin(GPIOBank::Bank1, 3),
+            io1_4: pin(GPIOBank::Bank1, 4),
+            io1_5: pin(GPIOBank::Bank1, 5),
+            io1_6: pin(GPIOBank::Bank1, 6),
+            io1_7: pin(GPIOBank::Bank1, 7),
+        })
+    }
 }
 
-impl<'a, I2C, E, Io> ExpanderInputPin<'a, I2C, Io>
+impl<'a, I2C, Io> ExpanderPins8<'a, I2C, Io>
 where
+    I2C: I2c,
     Io: SyncExpander<I2C>,
+{
+    /// Configures all pins of [`GPIOBank::Bank0`] as inputs and creates their pin structs.
+    pub(crate) fn new(expander: &'a Io) -> Result<Self, ExpanderError<I2C::Error>> {
+        expander.write_byte(Register::ConfigurationPort0, 0xFF)?;
+
+        let pin = |pin| ExpanderInputPin::from_parts(expander, GPIOBank::Bank0, pin);
+
+        Ok(Self {
+            io0_0: pin(0),
+            io0_1: pin(1),
+            io0_2: pin(2),
+            io0_3: pin(3),
+            io0_4: pin(4),
+            io0_5: pin(5),
+            io0_6: pin(6),
+            io0_7: pin(7),
+        })
+    }
+}
+
+impl<I2C, H> HandleInputPin<I2C, H>
+where
+    H: Deref,
+    H::Target: SyncExpander<I2C>,
+    I2C: I2c,
+{
+    /// Creates the pin struct without touching the device. The caller has to ensure that the pin is configured as input.
+    pub(crate) fn from_parts(expander: H, bank: GPIOBank, pin: u8) -> Self {
+        Self {
+            expander,
+            bank,
+            pin,
+            phantom_data: PhantomData,
+        }
+    }
+}
+
+impl<I2C, E, H> HandleInputPin<I2C, H>
+where
+    H: Deref,
+    H::Target: SyncExpander<I2C>,
     E: Debug,
     I2C: I2c<Error = E>,
 {
     /// Create a new input pin
     ///
-    /// # Panics
-    /// The function will panic if the provided pin is not in the allowed range of 0-7
-    pub fn new(expander: &'a Io, bank: GPIOBank, pin: u8) -> Result<Self, ExpanderError<E>> {
-        assert!(pin < 8);
-
-        let register = match bank {
-            GPIOBank::Bank0 => Register::ConfigurationPort0,
-            GPIOBank::Bank1 => Register::ConfigurationPort1,
-        };
-
-        let mut reg_val: u8 = 0x00;
-
-        expander.read_byte(register, &mut reg_val)?;
-        expander.write_byte(register, reg_val | (0x01 << pin))?;
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
+    pub fn new(expander: H, bank: GPIOBank, pin: u8) -> Result<Self, ExpanderError<E>> {
+        check_pin(pin)?;
+
+        modify_bit(
+            &*expander,
+            bank_register(bank, Register::ConfigurationPort0),
+            pin,
+            |reg_val, mask| reg_val | mask,
+        )?;
 
         Ok(Self {
             expander,
@@ -74,74 +295,51 @@ where
         })
     }
 
+    /// Create a new input pin from a [`PinId`], which is always within the allowed pin range.
+    pub fn from_pin_id(expander: H, pin: PinId) -> Result<Self, ExpanderError<E>> {
+        Self::new(expander, pin.bank(), pin.pin())
+    }
+
     /// Sets the polarity of the input pin. The input pins have normal polarity by default on device startup.
     ///
     /// If the polarity is [`Polarity::Normal`] a logic `high` voltage level on the input is detected as `high` in the software.
     ///
     /// If the polarity is [`Polarity::Inverse`] a logic `high` voltage level on the input is detected as `low` by the software.
     pub fn set_polarity(&mut self, polarity: Polarity) -> Result<(), ExpanderError<E>> {
-        let register = match self.bank {
-            GPIOBank::Bank0 => Register::PolarityInversionPort0,
-            GPIOBank::Bank1 => Register::PolarityInversionPort1,
-        };
-
-        let mut reg_val: u8 = 0x00;
-
-        self.expander.read_byte(register, &mut reg_val)?;
+        set_polarity(&*self.expander, self.bank, self.pin, polarity)
+    }
 
-        if let Polarity::Normal = polarity {
-            self.expander
-                .write_byte(register, reg_val & !(0x01 << self.pin))?;
-        } else {
-            self.expander
-                .write_byte(register, reg_val | (0x01 << self.pin))?;
-        }
+    /// Returns the pin as [`HandleFlexPin`] configured as input without accessing the device.
+    pub fn into_flex(self) -> HandleFlexPin<I2C, H> {
+        HandleFlexPin::from_parts(self.expander, self.bank, self.pin, Direction::Input)
+    }
 
-        Ok(())
+    /// Reconfigures the pin as output driving the given initial state and returns it as [`ExpanderOutputPin`].
+    pub fn into_output(self, state: PinState) -> Result<HandleOutputPin<I2C, H>, ExpanderError<E>> {
+        HandleOutputPin::new(self.expander, self.bank, self.pin, state)
     }
 }
 
-impl<'a, I2C, E, Io> ExpanderOutputPin<'a, I2C, Io>
+impl<I2C, E, H> HandleOutputPin<I2C, H>
 where
-    Io: SyncExpander<I2C>,
+    H: Deref,
+    H::Target: SyncExpander<I2C>,
     E: Debug,
     I2C: I2c<Error = E>,
 {
     /// Create a new output pin
     ///
-    /// # Panics
-    /// The function will panic if the provided pin is not in the allowed range of 0-7
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
     pub fn new(
-        expander: &'a Io,
+        expander: H,
         bank: GPIOBank,
         pin: u8,
         state: PinState,
     ) -> Result<Self, ExpanderError<E>> {
-        assert!(pin < 8);
+        check_pin(pin)?;
 
-        let cp_register = match bank {
-            GPIOBank::Bank0 => Register::ConfigurationPort0,
-            GPIOBank::Bank1 => Register::ConfigurationPort1,
-        };
-
-        let op_register = match bank {
-            GPIOBank::Bank0 => Register::OutputPort0,
-            GPIOBank::Bank1 => Register::OutputPort1,
-        };
-
-        let mut reg_val: u8 = 0x00;
-
-        expander.read_byte(op_register, &mut reg_val)?;
-
-        if let PinState::High = state {
-            expander.write_byte(op_register, reg_val | (0x01 << pin))?;
-        } else {
-            expander.write_byte(op_register, reg_val & !(0x01 << pin))?;
-        }
-
-        expander.read_byte(cp_register, &mut reg_val)?;
-
-        expander.write_byte(cp_register, reg_val & !(0x01 << pin))?;
+        set_as_output(&*expander, bank, pin, state)?;
 
         Ok(Self {
             expander,
@@ -150,96 +348,389 @@ where
             phantom_data: PhantomData,
         })
     }
+
+    /// Create a new output pin from a [`PinId`], which is always within the allowed pin range.
+    pub fn from_pin_id(expander: H, pin: PinId, state: PinState) -> Result<Self, ExpanderError<E>> {
+        Self::new(expander, pin.bank(), pin.pin(), state)
+    }
+
+    /// Returns the pin as [`HandleFlexPin`] configured as output without accessing the device.
+    pub fn into_flex(self) -> HandleFlexPin<I2C, H> {
+        HandleFlexPin::from_parts(self.expander, self.bank, self.pin, Direction::Output)
+    }
+
+    /// Reconfigures the pin as input and returns it as [`ExpanderInputPin`].
+    pub fn into_input(self) -> Result<HandleInputPin<I2C, H>, ExpanderError<E>> {
+        HandleInputPin::new(self.expander, self.bank, self.pin)
+    }
 }
 
-impl<'a, I2C, E, Io> ErrorType for ExpanderInputPin<'a, I2C, Io>
+impl<I2C, E, H> ErrorType for HandleInputPin<I2C, H>
 where
-    Io: SyncExpander<I2C>,
+    H: Deref,
+    H::Target: SyncExpander<I2C>,
     E: Debug,
     I2C: I2c<Error = E>,
 {
     type Error = ExpanderError<E>;
 }
 
-impl<'a, I2C, E, Io> InputPin for ExpanderInputPin<'a, I2C, Io>
+impl<I2C, E, H> InputPin for HandleInputPin<I2C, H>
 where
-    Io: SyncExpander<I2C>,
+    H: Deref,
+    H::Target: SyncExpander<I2C>,
     E: Debug,
     I2C: I2c<Error = E>,
 {
     fn is_high(&mut self) -> Result<bool, Self::Error> {
-        let register = match self.bank {
-            GPIOBank::Bank0 => Register::InputPort0,
-            GPIOBank::Bank1 => Register::InputPort1,
-        };
+        read_bit(
+            &*self.expander,
+            bank_register(self.bank, Register::InputPort0),
+            self.pin,
+        )
+    }
 
-        let mut reg_val: u8 = 0x00;
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.is_high()?)
+    }
+}
+
+impl<I2C, E, H> ErrorType for HandleOutputPin<I2C, H>
+where
+    H: Deref,
+    H::Target: SyncExpander<I2C>,
+    E: Debug,
+    I2C: I2c<Error = E>,
+{
+    type Error = ExpanderError<E>;
+}
+
+impl<I2C, E, H> OutputPin for HandleOutputPin<I2C, H>
+where
+    H: Deref,
+    H::Target: SyncExpander<I2C>,
+    E: Debug,
+    I2C: I2c<Error = E>,
+{
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        modify_bit(
+            &*self.expander,
+            bank_register(self.bank, Register::OutputPort0),
+            self.pin,
+            |reg_val, mask| reg_val & !mask,
+        )
+    }
 
-        self.expander.read_byte(register, &mut reg_val)?;
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        modify_bit(
+            &*self.expander,
+            bank_register(self.bank, Register::OutputPort0),
+            self.pin,
+            |reg_val, mask| reg_val | mask,
+        )
+    }
+}
 
-        match (reg_val >> self.pin) & 1 {
-            1 => Ok(true),
-            _ => Ok(false),
+impl<I2C, E, H> StatefulOutputPin for HandleOutputPin<I2C, H>
+where
+    H: Deref,
+    H::Target: SyncExpander<I2C>,
+    E: Debug,
+    I2C: I2c<Error = E>,
+{
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        read_bit(
+            &*self.expander,
+            bank_register(self.bank, Register::OutputPort0),
+            self.pin,
+        )
+    }
+
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.is_set_high()?)
+    }
+
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        modify_bit(
+            &*self.expander,
+            bank_register(self.bank, Register::OutputPort0),
+            self.pin,
+            |reg_val, mask| reg_val ^ mask,
+        )
+    }
+}
+
+impl<I2C, H> HandleFlexPin<I2C, H>
+where
+    H: Deref,
+    H::Target: SyncExpander<I2C>,
+    I2C: I2c,
+{
+    /// Creates the pin struct without touching the device. The caller has to ensure that the pin is configured in the given direction.
+    pub(crate) fn from_parts(expander: H, bank: GPIOBank, pin: u8, direction: Direction) -> Self {
+        Self {
+            expander,
+            bank,
+            pin,
+            direction,
+            phantom_data: PhantomData,
         }
     }
 
-    fn is_low(&mut self) -> Result<bool, Self::Error> {
-        let register = match self.bank {
-            GPIOBank::Bank0 => Register::InputPort0,
-            GPIOBank::Bank1 => Register::InputPort1,
+    /// Returns the direction the pin is currently configured in.
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+}
+
+impl<I2C, E, H> HandleFlexPin<I2C, H>
+where
+    H: Deref,
+    H::Target: SyncExpander<I2C>,
+    E: Debug,
+    I2C: I2c<Error = E>,
+{
+    /// Create a new flexible pin keeping the direction the pin is currently configured in on the device.
+    ///
+    /// # Errors
+    /// Returns [`ExpanderError::InvalidPin`] if the provided pin is not in the allowed range of 0-7
+    pub fn new(expander: H, bank: GPIOBank, pin: u8) -> Result<Self, ExpanderError<E>> {
+        check_pin(pin)?;
+
+        let direction = match read_bit(
+            &*expander,
+            bank_register(bank, Register::ConfigurationPort0),
+            pin,
+        )? {
+            true => Direction::Input,
+            false => Direction::Output,
         };
 
-        let mut reg_val: u8 = 0x00;
+        Ok(Self::from_parts(expander, bank, pin, direction))
+    }
+
+    /// Create a new flexible pin from a [`PinId`], which is always within the allowed pin range.
+    pub fn from_pin_id(expander: H, pin: PinId) -> Result<Self, ExpanderError<E>> {
+        Self::new(expander, pin.bank(), pin.pin())
+    }
+
+    /// Reconfigures the pin as input.
+    pub fn set_as_input(&mut self) -> Result<(), ExpanderError<E>> {
+        modify_bit(
+            &*self.expander,
+            bank_register(self.bank, Register::ConfigurationPort0),
+            self.pin,
+            |reg_val, mask| reg_val | mask,
+        )?;
+
+        self.direction = Direction::Input;
+
+        Ok(())
+    }
 
-        self.expander.read_byte(register, &mut reg_val)?;
+    /// Reconfigures the pin as output driving the given state. The state is written before changing the direction, so the pin does not glitch.
+    pub fn set_as_output(&mut self, state: PinState) -> Result<(), ExpanderError<E>> {
+        set_as_output(&*self.expander, self.bank, self.pin, state)?;
 
-        match (reg_val >> self.pin) & 1 {
-            1 => Ok(false),
-            _ => Ok(true),
+        self.direction = Direction::Output;
+
+        Ok(())
+    }
+
+    /// Sets the polarity of the pin while it is used as input. Please see [`HandleInputPin::set_polarity()`] for more information.
+    pub fn set_polarity(&mut self, polarity: Polarity) -> Result<(), ExpanderError<E>> {
+        set_polarity(&*self.expander, self.bank, self.pin, polarity)
+    }
+
+    /// Returns [`ExpanderError::DirectionMismatch`] if the pin is not configured in the given direction.
+    fn check_direction(&self, direction: Direction) -> Result<(), ExpanderError<E>> {
+        if self.direction == direction {
+            Ok(())
+        } else {
+            Err(ExpanderError::DirectionMismatch)
         }
     }
 }
 
-impl<'a, I2C, Io, E> ErrorType for ExpanderOutputPin<'a, I2C, Io>
+impl<I2C, E, H> ErrorType for HandleFlexPin<I2C, H>
 where
-    Io: SyncExpander<I2C>,
+    H: Deref,
+    H::Target: SyncExpander<I2C>,
     E: Debug,
     I2C: I2c<Error = E>,
 {
     type Error = ExpanderError<E>;
 }
 
-impl<'a, I2C, E, Io> OutputPin for ExpanderOutputPin<'a, I2C, Io>
+impl<I2C, E, H> InputPin for HandleFlexPin<I2C, H>
 where
-    Io: SyncExpander<I2C>,
+    H: Deref,
+    H::Target: SyncExpander<I2C>,
     E: Debug,
     I2C: I2c<Error = E>,
 {
-    fn set_low(&mut self) -> Result<(), Self::Error> {
-        let register = match self.bank {
-            GPIOBank::Bank0 => Register::OutputPort0,
-            GPIOBank::Bank1 => Register::OutputPort1,
-        };
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        self.check_direction(Direction::Input)?;
 
-        let mut reg_val: u8 = 0x00;
+        read_bit(
+            &*self.expander,
+            bank_register(self.bank, Register::InputPort0),
+            self.pin,
+        )
+    }
 
-        self.expander.read_byte(register, &mut reg_val)?;
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.is_high()?)
+    }
+}
 
-        self.expander
-            .write_byte(register, reg_val & !(0x01 << self.pin))
+impl<I2C, E, H> OutputPin for HandleFlexPin<I2C, H>
+where
+    H: Deref,
+    H::Target: SyncExpander<I2C>,
+    E: Debug,
+    I2C: I2c<Error = E>,
+{
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.check_direction(Direction::Output)?;
+        modify_bit(
+            &*self.expander,
+            bank_register(self.bank, Register::OutputPort0),
+            self.pin,
+            |reg_val, mask| reg_val & !mask,
+        )
     }
 
     fn set_high(&mut self) -> Result<(), Self::Error> {
-        let register = match self.bank {
-            GPIOBank::Bank0 => Register::OutputPort0,
-            GPIOBank::Bank1 => Register::OutputPort1,
-        };
+        self.check_direction(Direction::Output)?;
+        modify_bit(
+            &*self.expander,
+            bank_register(self.bank, Register::OutputPort0),
+            self.pin,
+            |reg_val, mask| reg_val | mask,
+        )
+    }
+}
+
+impl<I2C, E, H> StatefulOutputPin for HandleFlexPin<I2C, H>
+where
+    H: Deref,
+    H::Target: SyncExpander<I2C>,
+    E: Debug,
+    I2C: I2c<Error = E>,
+{
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        self.check_direction(Direction::Output)?;
+
+        read_bit(
+            &*self.expander,
+            bank_register(self.bank, Register::OutputPort0),
+            self.pin,
+        )
+    }
 
-        let mut reg_val: u8 = 0x00;
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.is_set_high()?)
+    }
 
-        self.expander.read_byte(register, &mut reg_val)?;
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        self.check_direction(Direction::Output)?;
+        modify_bit(
+            &*self.expander,
+            bank_register(self.bank, Register::OutputPort0),
+            self.pin,
+            |reg_val, mask| reg_val ^ mask,
+        )
+    }
+}
 
-        self.expander
-            .write_byte(register, reg_val | (0x01 << self.pin))
+/// Returns the register of the given bank, where `register` is the corresponding register of [`GPIOBank::Bank0`].
+fn bank_register(bank: GPIOBank, register: Register) -> Register {
+    match bank {
+        GPIOBank::Bank0 => register,
+        GPIOBank::Bank1 => register.get_neighbor(),
     }
 }
+
+/// Returns the bit of the given pin in the given register.
+fn read_bit<I2C, Ex>(
+    expander: &Ex,
+    register: Register,
+    pin: u8,
+) -> Result<bool, ExpanderError<I2C::Error>>
+where
+    I2C: I2c,
+    Ex: SyncExpander<I2C> + ?Sized,
+{
+    let mut reg_val: u8 = 0x00;
+
+    expander.read_byte(register, &mut reg_val)?;
+
+    Ok((reg_val >> pin) & 1 == 1)
+}
+
+/// Applies the given function to the given register, which receives the register value and the mask of the given pin.
+fn modify_bit<I2C, Ex>(
+    expander: &Ex,
+    register: Register,
+    pin: u8,
+    f: impl FnOnce(u8, u8) -> u8,
+) -> Result<(), ExpanderError<I2C::Error>>
+where
+    I2C: I2c,
+    Ex: SyncExpander<I2C> + ?Sized,
+{
+    let mask = 0x01 << pin;
+
+    expander.modify_byte(register, |reg_val| f(reg_val, mask))
+}
+
+/// Configures the given pin as output driving the given state. The state is written before changing the direction, so the pin does not glitch.
+fn set_as_output<I2C, Ex>(
+    expander: &Ex,
+    bank: GPIOBank,
+    pin: u8,
+    state: PinState,
+) -> Result<(), ExpanderError<I2C::Error>>
+where
+    I2C: I2c,
+    Ex: SyncExpander<I2C> + ?Sized,
+{
+    modify_bit(
+        expander,
+        bank_register(bank, Register::OutputPort0),
+        pin,
+        |reg_val, mask| match state {
+            PinState::High => reg_val | mask,
+            PinState::Low => reg_val & !mask,
+        },
+    )?;
+    modify_bit(
+        expander,
+        bank_register(bank, Register::ConfigurationPort0),
+        pin,
+        |reg_val, mask| reg_val & !mask,
+    )
+}
+
+/// Sets the input polarity of the given pin.
+fn set_polarity<I2C, Ex>(
+    expander: &Ex,
+    bank: GPIOBank,
+    pin: u8,
+    polarity: Polarity,
+) -> Result<(), ExpanderError<I2C::Error>>
+where
+    I2C: I2c,
+    Ex: SyncExpander<I2C> + ?Sized,
+{
+    modify_bit(
+        expander,
+        bank_register(bank, Register::PolarityInversionPort0),
+        pin,
+        |reg_val, mask| match polarity {
+            Polarity::Normal => reg_val & !mask,
+            Polarity::Inverse => reg_val | mask,
+        },
+    )
+}
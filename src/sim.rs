@@ -0,0 +1,446 @@
+//! Contains a software model of the PCA9535 to exercise the driver without any hardware.
+//!
+//! This module is only available if the "sim" feature of this crate is enabled.
+//!
+//! The [`SimDevice`] implements the [`hal`] I2C trait with the register file semantics of the real device. All clones of a [`SimDevice`] share the same device state,
+//! so one clone can be handed to an expander while another one is used to drive the virtual pin voltages and to inspect the device registers.
+//! ```
+//! use pca9535::sim::SimDevice;
+//! use pca9535::{GPIOBank, Pca9535Cached, PinState, StandardExpanderInterface};
+//!
+//! let device = SimDevice::new(32);
+//!
+//! let mut expander = Pca9535Cached::new(device.clone(), 32, device.interrupt_pin(), true).unwrap();
+//!
+//! device.drive_pin(GPIOBank::Bank0, 4, PinState::High);
+//! assert!(expander.pin_is_high(GPIOBank::Bank0, 4).unwrap());
+//!
+//! expander.pin_into_output(GPIOBank::Bank1, 2).unwrap();
+//! expander.pin_set_low(GPIOBank::Bank1, 2).unwrap();
+//! assert_eq!(device.pin_level(GPIOBank::Bank1, 2), PinState::Low);
+//! ```
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use hal::digital::{self, InputPin, OutputPin};
+use hal::i2c::{self, ErrorKind, I2c, NoAcknowledgeSource, Operation};
+
+use super::{ExtendedRegister, GPIOBank, PinState, Register};
+
+/// Simulated PCA9535 device implementing the [`I2c`] trait.
+///
+/// The device only answers to transactions addressed to its own address and otherwise reports [`NoAcknowledgeSource::Address`].
+/// The register file, pin levels and interrupt output behave as described in the device's documentation:
+/// - Registers are accessed via a command byte and the register pointer toggles between the two members of a register pair after each transferred data byte.
+/// - Writes to the input port registers are ignored, while invalid command bytes are not acknowledged.
+/// - Pins configured as outputs are driven to the level of the output port register, pins configured as inputs follow the level applied by [`SimDevice::drive_pin`].
+/// - The interrupt output is asserted once the level of any input pin differs from the level it had when its input port register was last read.
+///
+/// A device created by [`SimDevice::new_pcal9535a`] additionally provides the [`ExtendedRegister`]s of the PCAL9535A. Of those, the pull resistor configuration
+/// determines the level of input pins which are not driven from outside, the interrupt mask suppresses the interrupt of single pins and the interrupt status reports
+/// the pins currently causing an interrupt. All other extended registers only store the written values.
+///
+/// A device created by [`SimDevice::new_pca9554`] models an 8-bit expander, which only provides the registers of [`GPIOBank::Bank0`] using the command bytes `0x00-0x03`.
+/// Its register pointer does not advance after a transferred data byte. The registers of [`SimDevice::register`] keep using the numbering of [`Register`].
+#[derive(Debug, Clone)]
+pub struct SimDevice {
+    state: Arc<Mutex<SimState>>,
+}
+
+/// Interrupt output of a [`SimDevice`] implementing the [`InputPin`] trait.
+///
+/// The pin reads `low` as long as the simulated device asserts its open-drain interrupt output.
+#[derive(Debug, Clone)]
+pub struct SimInterruptPin {
+    state: Arc<Mutex<SimState>>,
+}
+
+/// Active-low RESET input of a [`SimDevice`].
+#[derive(Debug, Clone)]
+pub struct SimResetPin {
+    state: Arc<Mutex<SimState>>,
+}
+
+#[derive(Debug)]
+struct SimState {
+    address: u8,
+    registers: [u8; 8],
+    extended: Option<[u8; 16]>,
+    pointer: u8,
+    applied: [u8; 2],
+    driven: [u8; 2],
+    latched: [u8; 2],
+    eight_bit: bool,
+    in_reset: bool,
+    transactions: usize,
+}
+
+impl SimDevice {
+    /// Creates a new simulated device answering on the given address. All registers are in their power-on state and all input pins are driven `low`.
+    pub fn new(address: u8) -> Self {
+        Self::with_extended_registers(address, None)
+    }
+
+    /// Creates a new simulated 8-bit PCA9554 answering on the given address. All registers are in their power-on state and all input pins are driven `low`.
+    pub fn new_pca9554(address: u8) -> Self {
+        let device = Self::new(address);
+        device.lock().eight_bit = true;
+
+        device
+    }
+
+    /// Creates a new simulated PCAL9535A answering on the given address, which additionally provides the [`ExtendedRegister`]s.
+    /// All registers are in their power-on state and all input pins are driven `low`.
+    pub fn new_pcal9535a(address: u8) -> Self {
+        Self::with_extended_registers(address, Some([0x00; 16]))
+    }
+
+    fn with_extended_registers(address: u8, extended: Option<[u8; 16]>) -> Self {
+        let mut state = SimState {
+            address,
+            registers: [0x00; 8],
+            extended,
+            pointer: 0x00,
+            applied: [0x00; 2],
+            driven: [0xFF; 2],
+            latched: [0x00; 2],
+            eight_bit: false,
+            in_reset: false,
+            transactions: 0,
+        };
+        state.power_on_reset();
+
+        Self {
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    /// Returns the interrupt output of this device.
+    pub fn interrupt_pin(&self) -> SimInterruptPin {
+        SimInterruptPin {
+            state: self.state.clone(),
+        }
+    }
+
+    /// Returns the active-low RESET input of this device.
+    ///
+    /// While the pin is driven `low`, the device is held in its power-on state and does not acknowledge any transaction.
+    pub fn reset_pin(&self) -> SimResetPin {
+        SimResetPin {
+            state: self.state.clone(),
+        }
+    }
+
+    /// Applies the given voltage level to the given pin from outside of the device.
+    ///
+    /// The applied level only affects the pin if it is configured as input; outputs keep being driven by the device.
+    ///
+    /// # Panics
+    /// The function will panic if the provided pin is not in the allowed range of 0-7
+    pub fn drive_pin(&self, bank: GPIOBank, pin: u8, state: PinState) {
+        assert!(pin < 8);
+
+        let mut device = self.lock();
+
+        device.driven[bank as usize] |= 0x01 << pin;
+
+        match state {
+            PinState::High => device.applied[bank as usize] |= 0x01 << pin,
+            PinState::Low => device.applied[bank as usize] &= !(0x01 << pin),
+        }
+    }
+
+    /// Stops driving the given pin from outside of the device, leaving it floating.
+    ///
+    /// A floating input pin reads `low`, unless a pull resistor of a PCAL9535A is enabled for it.
+    ///
+    /// # Panics
+    /// The function will panic if the provided pin is not in the allowed range of 0-7
+    pub fn release_pin(&self, bank: GPIOBank, pin: u8) {
+        assert!(pin < 8);
+
+        self.lock().driven[bank as usize] &= !(0x01 << pin);
+    }
+
+    /// Returns the voltage level currently present at the given pin.
+    ///
+    /// # Panics
+    /// The function will panic if the provided pin is not in the allowed range of 0-7
+    pub fn pin_level(&self, bank: GPIOBank, pin: u8) -> PinState {
+        assert!(pin < 8);
+
+        PinState::from((self.lock().levels(bank) >> pin) & 1 == 1)
+    }
+
+    /// Returns the value of the given register as it would be read from the bus, without affecting the interrupt output.
+    pub fn register(&self, register: Register) -> u8 {
+        self.lock().peek(register as u8)
+    }
+
+    /// Returns the value of the given extended register as it would be read from the bus.
+    ///
+    /// # Panics
+    /// The function will panic if the device was not created using [`SimDevice::new_pcal9535a`]
+    pub fn extended_register(&self, register: ExtendedRegister) -> u8 {
+        let device = self.lock();
+        assert!(device.extended.is_some());
+
+        device.peek(register as u8)
+    }
+
+    /// Overwrites the given register, as another bus master or a glitch would do. Writes to the input port registers are ignored.
+    pub fn set_register(&self, register: Register, value: u8) {
+        self.lock().poke(register as u8, value);
+    }
+
+    /// Returns `true` if the device currently asserts its interrupt output.
+    pub fn interrupt_asserted(&self) -> bool {
+        self.lock().interrupt_asserted()
+    }
+
+    /// Returns the number of I2C transactions the device has seen so far, including the ones addressed to other devices.
+    pub fn transaction_count(&self) -> usize {
+        self.lock().transactions
+    }
+
+    /// Restores the power-on state of all registers, as a power cycle of the device would do.
+    pub fn power_on_reset(&self) {
+        self.lock().power_on_reset();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SimState> {
+        self.state.lock().unwrap()
+    }
+}
+
+impl SimState {
+    fn power_on_reset(&mut self) {
+        self.registers = [0x00, 0x00, 0xFF, 0xFF, 0x00, 0x00, 0xFF, 0xFF];
+        if let Some(extended) = self.extended.as_mut() {
+            *extended = [
+                0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00,
+                0x00, 0x00,
+            ];
+        }
+        self.pointer = 0x00;
+        self.latched = [self.levels(GPIOBank::Bank0), self.levels(GPIOBank::Bank1)];
+    }
+
+    /// Returns the voltage levels of all pins of the given bank.
+    fn levels(&self, bank: GPIOBank) -> u8 {
+        let output = self.registers[Register::OutputPort0 as usize + bank as usize];
+        let configuration = self.registers[Register::ConfigurationPort0 as usize + bank as usize];
+
+        let driven = self.driven[bank as usize];
+        let pulled_up = self.extended(ExtendedRegister::PullEnable0, bank)
+            & self.extended(ExtendedRegister::PullSelection0, bank);
+        let input = (self.applied[bank as usize] & driven) | (pulled_up & !driven);
+
+        (input & configuration) | (output & !configuration)
+    }
+
+    /// Returns the value of the given bank's member of an extended register pair, or `0x00` if the device has no extended registers.
+    fn extended(&self, register: ExtendedRegister, bank: GPIOBank) -> u8 {
+        self.extended.map_or(0x00, |extended| {
+            extended[(register as usize - 0x40) + bank as usize]
+        })
+    }
+
+    /// Returns the pins of the given bank currently causing an interrupt.
+    fn interrupt_status(&self, bank: GPIOBank) -> u8 {
+        let configuration = self.registers[Register::ConfigurationPort0 as usize + bank as usize];
+        let mask = self.extended(ExtendedRegister::InterruptMask0, bank);
+
+        (self.levels(bank) ^ self.latched[bank as usize]) & configuration & !mask
+    }
+
+    fn interrupt_asserted(&self) -> bool {
+        let banks = if self.eight_bit { 1 } else { 2 };
+
+        [GPIOBank::Bank0, GPIOBank::Bank1]
+            .into_iter()
+            .take(banks)
+            .any(|bank| self.interrupt_status(bank) != 0)
+    }
+
+    fn peek(&self, command: u8) -> u8 {
+        match command {
+            0x00 | 0x01 => {
+                let bank = if command == 0x00 {
+                    GPIOBank::Bank0
+                } else {
+                    GPIOBank::Bank1
+                };
+
+                self.levels(bank)
+                    ^ self.registers[Register::PolarityInversionPort0 as usize + bank as usize]
+            }
+            0x4C => self.interrupt_status(GPIOBank::Bank0),
+            0x4D => self.interrupt_status(GPIOBank::Bank1),
+            0x40..=0x4F => self
+                .extended
+                .map_or(0x00, |extended| extended[command as usize - 0x40]),
+            _ => self.registers[command as usize],
+        }
+    }
+
+    fn poke(&mut self, command: u8, value: u8) {
+        match command {
+            0x00 | 0x01 | 0x4C | 0x4D => {}
+            0x40..=0x4F => {
+                if let Some(extended) = self.extended.as_mut() {
+                    extended[command as usize - 0x40] = value;
+                }
+            }
+            _ => self.registers[command as usize] = value,
+        }
+    }
+
+    fn select(&mut self, command: u8) -> Result<(), ErrorKind> {
+        if self.eight_bit {
+            if command > 0x03 {
+                return Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data));
+            }
+
+            self.pointer = command * 2;
+            return Ok(());
+        }
+
+        let valid = match command {
+            0x00..=0x07 => true,
+            0x40..=0x4D | 0x4F => self.extended.is_some(),
+            _ => false,
+        };
+
+        if !valid {
+            return Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data));
+        }
+
+        self.pointer = command;
+        Ok(())
+    }
+
+    fn read_next(&mut self) -> u8 {
+        let value = self.peek(self.pointer);
+
+        if self.pointer < 0x02 {
+            self.latched[self.pointer as usize] = self.levels(if self.pointer == 0x00 {
+                GPIOBank::Bank0
+            } else {
+                GPIOBank::Bank1
+            });
+        }
+
+        self.advance();
+        value
+    }
+
+    fn write_next(&mut self, value: u8) {
+        self.poke(self.pointer, value);
+        self.advance();
+    }
+
+    /// Moves the register pointer to the other member of the current register pair. The output port configuration register is not part of a pair and 8-bit devices keep the pointer.
+    fn advance(&mut self) {
+        if !self.eight_bit && self.pointer != 0x4F {
+            self.pointer ^= 0x01;
+        }
+    }
+
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), ErrorKind> {
+        self.transactions += 1;
+
+        if address != self.address || self.in_reset {
+            return Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address));
+        }
+
+        // Adjacent write operations are not separated by a repeated start, so only the first byte of a write frame is a command byte.
+        let mut command_pending = true;
+
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => {
+                    for byte in bytes.iter() {
+                        if command_pending {
+                            self.select(*byte)?;
+                            command_pending = false;
+                        } else {
+                            self.write_next(*byte);
+                        }
+                    }
+                }
+                Operation::Read(buffer) => {
+                    for byte in buffer.iter_mut() {
+                        *byte = self.read_next();
+                    }
+                    command_pending = true;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl i2c::ErrorType for SimDevice {
+    type Error = ErrorKind;
+}
+
+impl I2c for SimDevice {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.lock().transaction(address, operations)
+    }
+}
+
+#[cfg(feature = "async")]
+impl hal_async::i2c::I2c for SimDevice {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.lock().transaction(address, operations)
+    }
+}
+
+impl digital::ErrorType for SimResetPin {
+    type Error = core::convert::Infallible;
+}
+
+impl OutputPin for SimResetPin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        let mut state = self.state.lock().unwrap();
+
+        state.power_on_reset();
+        state.in_reset = true;
+
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.state.lock().unwrap().in_reset = false;
+
+        Ok(())
+    }
+}
+
+impl digital::ErrorType for SimInterruptPin {
+    type Error = core::convert::Infallible;
+}
+
+impl InputPin for SimInterruptPin {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.state.lock().unwrap().interrupt_asserted())
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.state.lock().unwrap().interrupt_asserted())
+    }
+}
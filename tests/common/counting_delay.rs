@@ -0,0 +1,14 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// A delay counting the number of waits.
+pub struct CountingDelay(pub Rc<Cell<u32>>);
+
+impl hal::delay::DelayNs for CountingDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+
+    fn delay_us(&mut self, us: u32) {
+        assert_eq!(us, 50);
+        self.0.set(self.0.get() + 1);
+    }
+}
@@ -0,0 +1,16 @@
+/// Interrupt pin which fails on every read
+pub struct FaultyPin;
+
+impl hal::digital::ErrorType for FaultyPin {
+    type Error = hal::digital::ErrorKind;
+}
+
+impl hal::digital::InputPin for FaultyPin {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Err(hal::digital::ErrorKind::Other)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Err(hal::digital::ErrorKind::Other)
+    }
+}
@@ -0,0 +1,31 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use hal::i2c::{ErrorKind, I2c, Operation};
+use pca9535::sim::SimDevice;
+
+/// A bus failing the given number of transactions with the given error before passing them on to the device.
+pub struct FlakyBus {
+    pub device: SimDevice,
+    pub failures: Rc<Cell<usize>>,
+    pub error: ErrorKind,
+}
+
+impl hal::i2c::ErrorType for FlakyBus {
+    type Error = ErrorKind;
+}
+
+impl I2c for FlakyBus {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if self.failures.get() > 0 {
+            self.failures.set(self.failures.get() - 1);
+            return Err(self.error);
+        }
+
+        self.device.transaction(address, operations)
+    }
+}
@@ -0,0 +1,36 @@
+use hal::i2c::{ErrorKind, I2c, Operation};
+
+/// A device which auto-increments its register pointer, or fails every transaction with the given error.
+pub struct ForeignDevice(pub Option<ErrorKind>);
+
+impl hal::i2c::ErrorType for ForeignDevice {
+    type Error = ErrorKind;
+}
+
+impl I2c for ForeignDevice {
+    fn transaction(
+        &mut self,
+        _address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if let Some(error) = self.0 {
+            return Err(error);
+        }
+
+        let mut pointer = 0x00;
+
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => pointer = bytes[0],
+                Operation::Read(buffer) => {
+                    for byte in buffer.iter_mut() {
+                        *byte = pointer;
+                        pointer += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
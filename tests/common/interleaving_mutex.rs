@@ -0,0 +1,37 @@
+use std::cell::Cell;
+use std::sync::Mutex;
+
+use pca9535::{ExpanderMutex, GPIOBank, LockError, Pca9535Immediate, StandardExpanderInterface};
+
+use super::sim_bus::SimBus;
+
+/// Bus mutex which lets another user of the bus drive pin 1 of device 0 low right after the lock is released for the `N`-th time.
+pub struct InterleavingMutex<const N: usize = 1> {
+    bus: Mutex<SimBus>,
+    releases: Cell<usize>,
+}
+
+impl<const N: usize> ExpanderMutex<SimBus> for InterleavingMutex<N> {
+    fn lock<R, C: FnOnce(&mut SimBus) -> R>(&self, c: C) -> Result<R, LockError> {
+        let result = c(&mut self.bus.lock().unwrap());
+
+        self.releases.set(self.releases.get() + 1);
+
+        if self.releases.get() == N {
+            let mut bus = self.bus.lock().unwrap();
+
+            Pca9535Immediate::new(&mut *bus, 32)
+                .pin_set_low(GPIOBank::Bank0, 1)
+                .unwrap();
+        }
+
+        Ok(result)
+    }
+
+    fn new(bus: SimBus) -> Self {
+        Self {
+            bus: Mutex::new(bus),
+            releases: Cell::new(0),
+        }
+    }
+}
@@ -0,0 +1,16 @@
+/// Interrupt pin which panics on every read, poisoning the lock of a shared interrupt
+pub struct PanickingPin;
+
+impl hal::digital::ErrorType for PanickingPin {
+    type Error = hal::digital::ErrorKind;
+}
+
+impl hal::digital::InputPin for PanickingPin {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        panic!("interrupt pin read")
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        panic!("interrupt pin read")
+    }
+}
@@ -0,0 +1,26 @@
+use hal::i2c::{ErrorKind, I2c, NoAcknowledgeSource, Operation};
+use pca9535::sim::SimDevice;
+
+/// A bus shared by multiple simulated devices.
+pub struct SimBus(pub Vec<SimDevice>);
+
+impl hal::i2c::ErrorType for SimBus {
+    type Error = ErrorKind;
+}
+
+impl I2c for SimBus {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        for device in self.0.iter_mut() {
+            match device.transaction(address, operations) {
+                Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)) => {}
+                result => return result,
+            }
+        }
+
+        Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address))
+    }
+}
@@ -0,0 +1,34 @@
+use hal::i2c::{ErrorKind, I2c, Operation};
+use pca9535::sim::SimDevice;
+
+/// A bus on which the given data bits of all written bytes are stuck at `high`.
+pub struct StuckBitBus {
+    pub device: SimDevice,
+    pub stuck: u8,
+}
+
+impl hal::i2c::ErrorType for StuckBitBus {
+    type Error = ErrorKind;
+}
+
+impl I2c for StuckBitBus {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if let [Operation::Write(bytes)] = operations {
+            let mut corrupted = bytes.to_vec();
+
+            for byte in corrupted.iter_mut().skip(1) {
+                *byte |= self.stuck;
+            }
+
+            return self
+                .device
+                .transaction(address, &mut [Operation::Write(&corrupted)]);
+        }
+
+        self.device.transaction(address, operations)
+    }
+}
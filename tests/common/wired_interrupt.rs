@@ -0,0 +1,21 @@
+use std::convert::Infallible;
+
+use hal::digital::InputPin;
+use pca9535::sim::SimInterruptPin;
+
+/// Wire-OR of the open-drain interrupt outputs of multiple devices
+pub struct WiredInterrupt(pub Vec<SimInterruptPin>);
+
+impl hal::digital::ErrorType for WiredInterrupt {
+    type Error = Infallible;
+}
+
+impl InputPin for WiredInterrupt {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        self.is_low().map(|low| !low)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.0.iter_mut().any(|pin| pin.is_low().unwrap()))
+    }
+}
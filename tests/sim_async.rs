@@ -0,0 +1,89 @@
+use embassy_futures::block_on;
+use hal::digital::InputPin;
+use pca9535::sim::SimDevice;
+use pca9535::{
+    AsyncExpander, AsyncStandardExpanderInterface, GPIOBank, Pca9535CachedAsync,
+    Pca9535ImmediateAsync, PinState, Register,
+};
+
+const ADDR: u8 = 33;
+
+#[test]
+fn immediate_input_output() {
+    let device = SimDevice::new(ADDR);
+    let mut expander = Pca9535ImmediateAsync::new(device.clone(), ADDR);
+
+    block_on(async {
+        expander.pin_into_output(GPIOBank::Bank0, 3).await.unwrap();
+        expander.pin_set_low(GPIOBank::Bank0, 3).await.unwrap();
+
+        assert_eq!(device.pin_level(GPIOBank::Bank0, 3), PinState::Low);
+
+        device.drive_pin(GPIOBank::Bank1, 7, PinState::High);
+
+        assert!(expander.pin_is_high(GPIOBank::Bank1, 7).await.unwrap());
+    });
+}
+
+#[test]
+fn cached_read_write() {
+    let device = SimDevice::new(ADDR);
+
+    block_on(async {
+        let mut expander =
+            Pca9535CachedAsync::new(device.clone(), ADDR, device.interrupt_pin(), false)
+                .await
+                .unwrap();
+
+        expander
+            .write_halfword(Register::PolarityInversionPort0, 0xABCD)
+            .await
+            .unwrap();
+
+        let mut buffer: u16 = 0;
+
+        expander
+            .read_halfword(Register::InputPort0, &mut buffer)
+            .await
+            .unwrap();
+
+        assert_eq!(buffer, 0xABCD);
+
+        device.drive_pin(GPIOBank::Bank0, 0, PinState::High);
+
+        assert!(expander.pin_is_low(GPIOBank::Bank0, 0).await.unwrap());
+    });
+}
+
+#[test]
+fn cached_interrupt_reads_both_inputs() {
+    let device = SimDevice::new(ADDR);
+
+    block_on(async {
+        let mut expander =
+            Pca9535CachedAsync::new(device.clone(), ADDR, device.interrupt_pin(), false)
+                .await
+                .unwrap();
+
+        device.drive_pin(GPIOBank::Bank1, 5, PinState::High);
+
+        let mut interrupt = device.interrupt_pin();
+
+        assert!(interrupt.is_low().unwrap());
+
+        let mut buffer: u8 = 0;
+
+        expander
+            .read_byte(Register::InputPort0, &mut buffer)
+            .await
+            .unwrap();
+
+        assert_eq!(buffer, 0x00);
+        assert!(interrupt.is_high().unwrap());
+
+        let transactions = device.transaction_count();
+
+        assert!(expander.pin_is_high(GPIOBank::Bank1, 5).await.unwrap());
+        assert_eq!(device.transaction_count(), transactions);
+    });
+}
@@ -0,0 +1,634 @@
+mod common {
+    pub mod faulty_pin;
+    pub mod flaky_bus;
+    pub mod panicking_pin;
+    pub mod stuck_bit_bus;
+    pub mod wired_interrupt;
+}
+
+use std::cell::Cell;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::rc::Rc;
+use std::sync::Mutex;
+
+use common::faulty_pin::FaultyPin;
+use common::flaky_bus::FlakyBus;
+use common::panicking_pin::PanickingPin;
+use common::stuck_bit_bus::StuckBitBus;
+use common::wired_interrupt::WiredInterrupt;
+
+use hal::digital::InputPin;
+use hal::i2c::ErrorKind;
+use pca9535::chip::{Pca9539, Tca9555};
+use pca9535::sim::SimDevice;
+use pca9535::{
+    Chip, Expander, ExpanderError, GPIOBank, InputChanges, LockError, NoDelay, NoInterruptPin,
+    Pca9535Cached, PinState, PollingInterrupt, Register, RetryPolicy, SharedInterrupt,
+    SharedInterruptError, StandardExpanderInterface, VerifyPolicy, WritePolicy,
+};
+
+const ADDR: u8 = 33;
+
+#[test]
+fn cached_init_reads_device_state() {
+    let device = SimDevice::new(ADDR);
+    device.set_register(Register::OutputPort0, 0x12);
+    device.set_register(Register::ConfigurationPort1, 0x34);
+
+    let mut expander =
+        Pca9535Cached::new(device.clone(), ADDR, device.interrupt_pin(), false).unwrap();
+    let transactions = device.transaction_count();
+    let mut buffer: u8 = 0;
+
+    expander
+        .read_byte(Register::OutputPort0, &mut buffer)
+        .unwrap();
+    assert_eq!(buffer, 0x12);
+
+    expander
+        .read_byte(Register::ConfigurationPort1, &mut buffer)
+        .unwrap();
+    assert_eq!(buffer, 0x34);
+
+    assert_eq!(device.transaction_count(), transactions);
+}
+
+#[test]
+fn cached_input_changes() {
+    let device = SimDevice::new(ADDR);
+    let mut expander =
+        Pca9535Cached::new(device.clone(), ADDR, device.interrupt_pin(), true).unwrap();
+
+    let transactions = device.transaction_count();
+
+    assert!(expander.input_changes().unwrap().is_empty());
+    assert_eq!(device.transaction_count(), transactions);
+
+    device.drive_pin(GPIOBank::Bank0, 3, PinState::High);
+    device.drive_pin(GPIOBank::Bank1, 7, PinState::High);
+
+    let changes = expander.input_changes().unwrap();
+
+    assert_eq!(changes.changed, 0x8008);
+    assert_eq!(changes.rising, 0x8008);
+    assert_eq!(changes.falling, 0x0000);
+    assert_eq!(device.transaction_count(), transactions + 1);
+    assert!(!device.interrupt_asserted());
+
+    device.drive_pin(GPIOBank::Bank0, 3, PinState::Low);
+    device.drive_pin(GPIOBank::Bank1, 0, PinState::High);
+
+    let changes = expander.input_changes().unwrap();
+
+    assert_eq!(changes.changed, 0x0108);
+    assert_eq!(changes.rising, 0x0100);
+    assert_eq!(changes.falling, 0x0008);
+
+    device.drive_pin(GPIOBank::Bank0, 5, PinState::High);
+
+    assert!(!expander.verify_cache().unwrap().is_empty());
+    assert!(!device.interrupt_asserted());
+
+    let changes = expander.input_changes().unwrap();
+
+    assert_eq!(changes.changed, 0x0020);
+    assert_eq!(changes.rising, 0x0020);
+    assert!(expander.input_changes().unwrap().is_empty());
+}
+
+#[test]
+fn faulty_interrupt_pin_reports_error() {
+    let device = SimDevice::new(ADDR);
+    let mut expander = Pca9535Cached::new(device, ADDR, FaultyPin, true).unwrap();
+    let mut buffer: u8 = 0;
+
+    assert!(matches!(
+        expander.read_byte(Register::InputPort0, &mut buffer),
+        Err(ExpanderError::InterruptPinError(
+            hal::digital::ErrorKind::Other
+        ))
+    ));
+
+    // Reads of registers other than the input registers do not depend on the interrupt pin
+    expander
+        .read_byte(Register::OutputPort0, &mut buffer)
+        .unwrap();
+
+    assert!(expander.input_changes().is_err());
+}
+
+#[test]
+fn chip_variants() {
+    let device = SimDevice::new(ADDR);
+    let transactions = device.transaction_count();
+    let mut expander =
+        Pca9535Cached::with_chip(device.clone(), ADDR, device.interrupt_pin(), true, Tca9555)
+            .unwrap();
+    let mut buffer: u8 = 0;
+
+    expander
+        .read_byte(Register::ConfigurationPort0, &mut buffer)
+        .unwrap();
+    assert_eq!(
+        buffer,
+        Tca9555::power_on_default(Register::ConfigurationPort0)
+    );
+    assert_eq!(device.transaction_count(), transactions);
+}
+
+#[test]
+fn cache_verification() {
+    let device = SimDevice::new(ADDR);
+    let mut expander =
+        Pca9535Cached::new(device.clone(), ADDR, device.interrupt_pin(), true).unwrap();
+
+    assert!(expander.verify_cache().unwrap().is_empty());
+
+    device.set_register(Register::OutputPort1, 0x0F);
+    device.set_register(Register::ConfigurationPort0, 0x00);
+
+    let mismatches = expander.verify_cache().unwrap();
+
+    assert!(mismatches.contains(Register::OutputPort1));
+    assert_eq!(mismatches.get(Register::OutputPort1), Some((0xFF, 0x0F)));
+    assert_eq!(
+        mismatches.get(Register::ConfigurationPort0),
+        Some((0xFF, 0x00))
+    );
+    assert_eq!(mismatches.get(Register::OutputPort0), None);
+    // Bank 0 now drives its high output levels, which differ from the cached input levels
+    assert_eq!(mismatches.get(Register::InputPort0), Some((0x00, 0xFF)));
+    assert_eq!(mismatches.iter().count(), 3);
+
+    expander.resync().unwrap();
+
+    assert!(expander.verify_cache().unwrap().is_empty());
+
+    let mut buffer: u8 = 0;
+
+    expander.set_verify_policy(VerifyPolicy::Report(3));
+    device.set_register(Register::PolarityInversionPort0, 0x01);
+
+    expander
+        .read_byte(Register::OutputPort0, &mut buffer)
+        .unwrap();
+    expander
+        .read_byte(Register::OutputPort0, &mut buffer)
+        .unwrap();
+
+    assert!(matches!(
+        expander.read_byte(Register::OutputPort0, &mut buffer),
+        Err(ExpanderError::CacheMismatch {
+            register: Register::PolarityInversionPort0,
+            cached: 0x00,
+            actual: 0x01,
+        })
+    ));
+
+    expander
+        .read_byte(Register::PolarityInversionPort0, &mut buffer)
+        .unwrap();
+    assert_eq!(buffer, 0x01);
+
+    expander.set_verify_policy(VerifyPolicy::Resync(1));
+    device.set_register(Register::OutputPort0, 0x00);
+
+    expander
+        .read_byte(Register::OutputPort0, &mut buffer)
+        .unwrap();
+    assert_eq!(buffer, 0x00);
+}
+
+#[test]
+fn cached_without_interrupt_pin() {
+    let device = SimDevice::new(ADDR);
+    let mut expander = Pca9535Cached::new(device.clone(), ADDR, NoInterruptPin, false).unwrap();
+    let mut buffer: u8 = 0;
+
+    let transactions = device.transaction_count();
+
+    expander
+        .read_byte(Register::ConfigurationPort1, &mut buffer)
+        .unwrap();
+    assert_eq!(device.transaction_count(), transactions);
+
+    device.drive_pin(GPIOBank::Bank1, 4, PinState::High);
+
+    assert!(expander.pin_is_high(GPIOBank::Bank1, 4).unwrap());
+    assert_eq!(device.transaction_count(), transactions + 1);
+
+    let now = Rc::new(Cell::new(0_u64));
+    let clock = {
+        let now = now.clone();
+        move || now.get()
+    };
+    let mut expander = Pca9535Cached::new(
+        device.clone(),
+        ADDR,
+        PollingInterrupt::new(clock, 10),
+        false,
+    )
+    .unwrap();
+
+    device.drive_pin(GPIOBank::Bank0, 1, PinState::High);
+
+    assert!(expander.pin_is_high(GPIOBank::Bank0, 1).unwrap());
+
+    device.drive_pin(GPIOBank::Bank0, 1, PinState::Low);
+    now.set(9);
+    let transactions = device.transaction_count();
+
+    assert!(expander.pin_is_high(GPIOBank::Bank0, 1).unwrap());
+    assert_eq!(device.transaction_count(), transactions);
+
+    now.set(10);
+
+    assert!(expander.pin_is_low(GPIOBank::Bank0, 1).unwrap());
+    assert_eq!(device.transaction_count(), transactions + 1);
+}
+
+#[test]
+fn write_back_policy() {
+    let device = SimDevice::new(ADDR);
+    let mut expander =
+        Pca9535Cached::new(device.clone(), ADDR, device.interrupt_pin(), true).unwrap();
+
+    expander.set_write_policy(WritePolicy::WriteBack).unwrap();
+
+    let transactions = device.transaction_count();
+
+    expander.pins_into_output(0x0102).unwrap();
+    expander.pin_set_low(GPIOBank::Bank0, 1).unwrap();
+    expander.pin_set_low(GPIOBank::Bank1, 0).unwrap();
+    expander.pin_set_high(GPIOBank::Bank0, 1).unwrap();
+
+    assert!(expander.is_dirty());
+    assert!(expander.pin_is_set_high(GPIOBank::Bank0, 1).unwrap());
+    assert_eq!(device.transaction_count(), transactions);
+    assert_eq!(device.register(Register::ConfigurationPort0), 0xFF);
+    assert!(expander.verify_cache().unwrap().is_empty());
+
+    let transactions = device.transaction_count();
+
+    expander.flush().unwrap();
+
+    assert!(!expander.is_dirty());
+    assert_eq!(device.transaction_count(), transactions + 2);
+    assert_eq!(device.register(Register::OutputPort0), 0xFF);
+    assert_eq!(device.register(Register::OutputPort1), 0xFE);
+    assert_eq!(device.register(Register::ConfigurationPort0), 0xFD);
+    assert_eq!(device.register(Register::ConfigurationPort1), 0xFE);
+    assert_eq!(device.pin_level(GPIOBank::Bank1, 0), PinState::Low);
+
+    expander.pin_set_low(GPIOBank::Bank1, 1).unwrap();
+    expander
+        .set_write_policy(WritePolicy::WriteThrough)
+        .unwrap();
+
+    assert!(!expander.is_dirty());
+    assert_eq!(device.register(Register::OutputPort1), 0xFC);
+
+    expander.set_write_policy(WritePolicy::WriteBack).unwrap();
+    expander.set_verify_policy(VerifyPolicy::Resync(1));
+    expander.pin_set_low(GPIOBank::Bank0, 0).unwrap();
+    device.set_register(Register::PolarityInversionPort1, 0x10);
+
+    let mut buffer: u8 = 0;
+
+    expander
+        .read_byte(Register::PolarityInversionPort1, &mut buffer)
+        .unwrap();
+
+    assert!(expander.is_dirty());
+    assert!(expander.pin_is_set_low(GPIOBank::Bank0, 0).unwrap());
+    assert_eq!(device.register(Register::OutputPort0), 0xFF);
+
+    expander
+        .read_byte(Register::PolarityInversionPort1, &mut buffer)
+        .unwrap();
+    assert_eq!(buffer, 0x10);
+}
+
+#[test]
+fn shared_interrupt_line() {
+    let devices = [
+        SimDevice::new(ADDR),
+        SimDevice::new(ADDR + 1),
+        SimDevice::new(ADDR + 2),
+    ];
+    let interrupt: SharedInterrupt<_, Mutex<_>> = SharedInterrupt::new(WiredInterrupt(
+        devices.iter().map(SimDevice::interrupt_pin).collect(),
+    ));
+
+    let mut expanders = [ADDR, ADDR + 1, ADDR + 2].map(|address| {
+        let device = devices[(address - ADDR) as usize].clone();
+        Pca9535Cached::new(device, address, interrupt.pin(), true).unwrap()
+    });
+    let [expander_0, expander_1, expander_2] = &mut expanders;
+
+    assert_eq!(
+        interrupt
+            .dispatch([&mut *expander_0, &mut *expander_1, &mut *expander_2])
+            .unwrap(),
+        [InputChanges::default(); 3]
+    );
+
+    devices[1].drive_pin(GPIOBank::Bank1, 5, PinState::High);
+
+    assert!(interrupt.is_active().unwrap());
+
+    let transactions = devices[2].transaction_count();
+    let [changes_0, changes_1, changes_2] = interrupt
+        .dispatch([&mut *expander_0, &mut *expander_1, &mut *expander_2])
+        .unwrap();
+
+    assert!(changes_0.is_empty());
+    assert_eq!(changes_1.rising, 0x2000);
+    assert!(changes_2.is_empty());
+    assert_eq!(devices[2].transaction_count(), transactions);
+    assert!(!interrupt.is_active().unwrap());
+
+    let transactions = devices[1].transaction_count();
+
+    assert!(expander_1.pin_is_high(GPIOBank::Bank1, 5).unwrap());
+    assert_eq!(devices[1].transaction_count(), transactions);
+}
+
+#[test]
+fn shared_interrupt_lock_error() {
+    let device = SimDevice::new(ADDR);
+    let interrupt: SharedInterrupt<_, Mutex<_>> = SharedInterrupt::new(PanickingPin);
+
+    assert!(catch_unwind(AssertUnwindSafe(|| interrupt.is_active())).is_err());
+    assert_eq!(
+        interrupt.is_active(),
+        Err(SharedInterruptError::Lock(LockError::Poisoned))
+    );
+    assert_eq!(
+        interrupt.pin().is_high(),
+        Err(SharedInterruptError::Lock(LockError::Poisoned))
+    );
+
+    let mut expander = Pca9535Cached::new(device, ADDR, interrupt.pin(), false).unwrap();
+
+    assert!(matches!(
+        interrupt.dispatch([&mut expander]),
+        Err(ExpanderError::LockError(LockError::Poisoned))
+    ));
+}
+
+#[test]
+fn reset_and_restore() {
+    let device = SimDevice::new(116);
+    let mut expander =
+        Pca9535Cached::with_chip(device.clone(), 116, device.interrupt_pin(), true, Pca9539)
+            .unwrap()
+            .with_reset_pin(device.reset_pin())
+            .unwrap();
+
+    expander.pins_into_output(0x00F0).unwrap();
+    expander.write_outputs(0x0050).unwrap();
+    expander.pin_inverse_polarity(GPIOBank::Bank1, 0).unwrap();
+
+    device.power_on_reset();
+    assert_eq!(device.register(Register::ConfigurationPort0), 0xFF);
+
+    expander.restore().unwrap();
+
+    assert_eq!(device.register(Register::OutputPort0), 0x50);
+    assert_eq!(device.register(Register::PolarityInversionPort1), 0x01);
+    assert_eq!(device.register(Register::ConfigurationPort0), 0x0F);
+    assert_eq!(device.pin_level(GPIOBank::Bank0, 4), PinState::High);
+    assert_eq!(device.pin_level(GPIOBank::Bank0, 5), PinState::Low);
+
+    expander.reset(&mut NoDelay).unwrap();
+
+    assert_eq!(device.register(Register::ConfigurationPort0), 0xFF);
+    assert_eq!(device.register(Register::OutputPort0), 0xFF);
+
+    let transactions = device.transaction_count();
+    let mut buffer: u8 = 0;
+
+    expander
+        .read_byte(Register::ConfigurationPort0, &mut buffer)
+        .unwrap();
+    assert_eq!(buffer, 0xFF);
+    expander
+        .read_byte(Register::PolarityInversionPort1, &mut buffer)
+        .unwrap();
+    assert_eq!(buffer, 0x00);
+    assert_eq!(device.transaction_count(), transactions);
+
+    let (mut expander, _reset_pin) = expander.release_reset_pin();
+
+    assert!(expander.verify_cache().unwrap().is_empty());
+}
+
+#[test]
+fn retry_policy() {
+    let device = SimDevice::new(ADDR);
+    device.set_register(Register::OutputPort0, 0xFD);
+    device.set_register(Register::ConfigurationPort0, 0xFD);
+
+    let bus_failures = Rc::new(Cell::new(0));
+    let bus = FlakyBus {
+        device: device.clone(),
+        failures: bus_failures.clone(),
+        error: ErrorKind::Bus,
+    };
+    let mut cached = Pca9535Cached::new(bus, ADDR, NoInterruptPin, false)
+        .unwrap()
+        .with_retry_policy(RetryPolicy::new(2));
+
+    bus_failures.set(1);
+    cached.pin_set_high(GPIOBank::Bank0, 1).unwrap();
+
+    assert_eq!(device.pin_level(GPIOBank::Bank0, 1), PinState::High);
+
+    bus_failures.set(1);
+    device.drive_pin(GPIOBank::Bank1, 0, PinState::High);
+
+    assert!(cached.pin_is_high(GPIOBank::Bank1, 0).unwrap());
+    assert_eq!(bus_failures.get(), 0);
+
+    bus_failures.set(2);
+
+    assert!(matches!(
+        cached.pin_is_high(GPIOBank::Bank1, 0),
+        Err(ExpanderError::WriteReadError(ErrorKind::Bus))
+    ));
+}
+
+#[test]
+fn write_verification() {
+    let device = SimDevice::new(ADDR);
+
+    let bus = StuckBitBus {
+        device: device.clone(),
+        stuck: 0x80,
+    };
+    let mut cached = Pca9535Cached::new(bus, ADDR, NoInterruptPin, false).unwrap();
+
+    cached.set_write_verification(true);
+
+    assert!(matches!(
+        cached.write_halfword(Register::ConfigurationPort0, 0x7F7F),
+        Err(ExpanderError::WriteVerifyMismatch {
+            register: Register::ConfigurationPort0,
+            expected: 0x7F,
+            actual: 0xFF
+        })
+    ));
+
+    // The cache takes over the actual device state.
+    assert!(cached.verify_cache().unwrap().is_empty());
+
+    cached.set_write_policy(WritePolicy::WriteBack).unwrap();
+    cached.write_byte(Register::OutputPort1, 0x00).unwrap();
+
+    assert!(matches!(
+        cached.flush(),
+        Err(ExpanderError::WriteVerifyMismatch {
+            register: Register::OutputPort1,
+            expected: 0x00,
+            actual: 0x80
+        })
+    ));
+    assert!(!cached.is_dirty());
+    assert!(cached.pin_is_set_high(GPIOBank::Bank1, 7).unwrap());
+
+    let bus = StuckBitBus {
+        device: device.clone(),
+        stuck: 0x01,
+    };
+    let mut cached = Pca9535Cached::new(bus, ADDR, NoInterruptPin, false).unwrap();
+
+    cached.set_write_verification(true);
+
+    assert!(cached
+        .write_halfword(Register::OutputPort0, 0x00FE)
+        .is_err());
+    assert!(cached.verify_cache().unwrap().is_empty());
+
+    // Only the first register mismatches, the cache of the second one still takes over the written value.
+    assert!(matches!(
+        cached.write_halfword(Register::OutputPort0, 0x0001),
+        Err(ExpanderError::WriteVerifyMismatch {
+            register: Register::OutputPort0,
+            expected: 0x00,
+            actual: 0x01
+        })
+    ));
+    assert!(cached.verify_cache().unwrap().is_empty());
+}
+
+#[cfg(test)]
+mod standard {
+    use super::ADDR;
+
+    use pca9535::sim::SimDevice;
+    use pca9535::{GPIOBank, Pca9535Cached, PinState, Register, StandardExpanderInterface};
+
+    #[test]
+    fn cached_bulk_operations() {
+        let device = SimDevice::new(ADDR);
+        let mut expander =
+            Pca9535Cached::new(device.clone(), ADDR, device.interrupt_pin(), true).unwrap();
+
+        expander.pins_into_output(0xFFFF).unwrap();
+
+        let transactions = device.transaction_count();
+
+        expander.pins_set_low(0x00FF).unwrap();
+
+        // The output registers are read from the cache
+        assert_eq!(device.transaction_count(), transactions + 1);
+        assert_eq!(device.register(Register::OutputPort0), 0x00);
+        assert_eq!(device.register(Register::OutputPort1), 0xFF);
+    }
+
+    #[test]
+    fn cached_input_polarity() {
+        let device = SimDevice::new(ADDR);
+        let mut expander =
+            Pca9535Cached::new(device.clone(), ADDR, device.interrupt_pin(), false).unwrap();
+
+        device.drive_pin(GPIOBank::Bank1, 0, PinState::High);
+        device.drive_pin(GPIOBank::Bank1, 1, PinState::High);
+
+        // Check internal Input register cache logic on polarity change
+        expander.inverse_polarity().unwrap();
+        expander.normal_polarity().unwrap();
+        expander.inverse_polarity().unwrap();
+
+        assert!(!expander.pin_is_high(GPIOBank::Bank1, 0).unwrap());
+        assert!(!expander.pin_is_high(GPIOBank::Bank1, 1).unwrap());
+
+        device.drive_pin(GPIOBank::Bank1, 0, PinState::Low);
+
+        assert!(expander.pin_is_high(GPIOBank::Bank1, 0).unwrap());
+
+        expander.pin_normal_polarity(GPIOBank::Bank1, 0).unwrap();
+
+        assert!(!expander.pin_is_high(GPIOBank::Bank1, 0).unwrap());
+        assert!(!expander.pin_is_high(GPIOBank::Bank1, 1).unwrap());
+    }
+
+    #[test]
+    fn cached_reads_without_interrupt_skip_the_bus() {
+        let device = SimDevice::new(ADDR);
+        let mut expander =
+            Pca9535Cached::new(device.clone(), ADDR, device.interrupt_pin(), true).unwrap();
+
+        let transactions = device.transaction_count();
+
+        assert!(expander.pin_is_low(GPIOBank::Bank0, 5).unwrap());
+        assert_eq!(device.transaction_count(), transactions);
+
+        device.drive_pin(GPIOBank::Bank0, 5, PinState::High);
+
+        assert!(expander.pin_is_high(GPIOBank::Bank0, 5).unwrap());
+        assert_eq!(device.transaction_count(), transactions + 1);
+        assert!(!device.interrupt_asserted());
+    }
+}
+
+#[cfg(test)]
+mod pin {
+    use std::sync::Mutex;
+
+    use super::ADDR;
+
+    use hal::digital::{InputPin, OutputPin};
+    use pca9535::sim::SimDevice;
+    use pca9535::{
+        ExpanderInputPin, ExpanderOutputPin, GPIOBank, IoExpander, Pca9535Cached, PinState,
+        Polarity,
+    };
+
+    #[test]
+    fn cached_hal_pins() {
+        let device = SimDevice::new(ADDR);
+        let expander =
+            Pca9535Cached::new(device.clone(), ADDR, device.interrupt_pin(), false).unwrap();
+        let io_expander: IoExpander<_, _, Mutex<_>> = IoExpander::new(expander);
+
+        let mut input = ExpanderInputPin::new(&io_expander, GPIOBank::Bank0, 4).unwrap();
+        let mut output =
+            ExpanderOutputPin::new(&io_expander, GPIOBank::Bank1, 5, PinState::High).unwrap();
+
+        assert_eq!(device.pin_level(GPIOBank::Bank1, 5), PinState::High);
+
+        output.set_low().unwrap();
+
+        assert_eq!(device.pin_level(GPIOBank::Bank1, 5), PinState::Low);
+
+        device.drive_pin(GPIOBank::Bank0, 4, PinState::High);
+
+        assert!(input.is_high().unwrap());
+
+        input.set_polarity(Polarity::Inverse).unwrap();
+
+        assert!(input.is_low().unwrap());
+    }
+}
@@ -0,0 +1,84 @@
+use pca9535::sim::SimDevice;
+use pca9535::{Expander, GPIOBank, Pca9535Immediate, PinState, Register};
+
+const ADDR: u8 = 33;
+
+#[test]
+fn read_write_halfword_pairs() {
+    let device = SimDevice::new(ADDR);
+    let mut expander = Pca9535Immediate::new(device.clone(), ADDR);
+
+    expander
+        .write_halfword(Register::OutputPort1, 0x4A07)
+        .unwrap();
+
+    assert_eq!(device.register(Register::OutputPort0), 0x07);
+    assert_eq!(device.register(Register::OutputPort1), 0x4A);
+
+    let mut buffer: u16 = 0;
+
+    expander
+        .read_halfword(Register::OutputPort0, &mut buffer)
+        .unwrap();
+
+    assert_eq!(buffer, 0x074A);
+}
+
+#[test]
+fn input_registers_are_read_only() {
+    let device = SimDevice::new(ADDR);
+    let mut expander = Pca9535Immediate::new(device.clone(), ADDR);
+
+    device.drive_pin(GPIOBank::Bank0, 1, PinState::High);
+    expander.write_byte(Register::InputPort0, 0xF0).unwrap();
+
+    let mut buffer: u8 = 0;
+
+    expander
+        .read_byte(Register::InputPort0, &mut buffer)
+        .unwrap();
+
+    assert_eq!(buffer, 0x02);
+}
+
+#[test]
+fn wrong_address_is_not_acknowledged() {
+    let device = SimDevice::new(ADDR);
+    let mut expander = Pca9535Immediate::new(device, ADDR + 1);
+
+    assert!(expander.write_byte(Register::OutputPort0, 0x00).is_err());
+}
+
+#[test]
+fn interrupt_follows_input_changes() {
+    let device = SimDevice::new(ADDR);
+    let mut expander = Pca9535Immediate::new(device.clone(), ADDR);
+    let mut buffer: u8 = 0;
+
+    assert!(!device.interrupt_asserted());
+
+    device.drive_pin(GPIOBank::Bank1, 3, PinState::High);
+    assert!(device.interrupt_asserted());
+
+    // Returning to the previous level clears the interrupt
+    device.drive_pin(GPIOBank::Bank1, 3, PinState::Low);
+    assert!(!device.interrupt_asserted());
+
+    device.drive_pin(GPIOBank::Bank1, 3, PinState::High);
+    expander
+        .read_byte(Register::InputPort0, &mut buffer)
+        .unwrap();
+    assert!(device.interrupt_asserted());
+
+    expander
+        .read_byte(Register::InputPort1, &mut buffer)
+        .unwrap();
+    assert!(!device.interrupt_asserted());
+
+    // Pins configured as outputs never trigger an interrupt
+    expander
+        .write_byte(Register::ConfigurationPort0, 0x00)
+        .unwrap();
+    expander.write_byte(Register::OutputPort0, 0x00).unwrap();
+    assert!(!device.interrupt_asserted());
+}
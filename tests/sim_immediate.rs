@@ -0,0 +1,660 @@
+mod common {
+    pub mod counting_delay;
+    pub mod flaky_bus;
+    pub mod stuck_bit_bus;
+}
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::Mutex;
+
+use common::counting_delay::CountingDelay;
+use common::flaky_bus::FlakyBus;
+use common::stuck_bit_bus::StuckBitBus;
+
+use hal::digital::{InputPin, OutputPin};
+use hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use pca9535::chip::Pca9539;
+use pca9535::sim::SimDevice;
+use pca9535::{
+    Chip, Expander, ExpanderError, ExpanderInputPin, ExpanderOutputPin, GPIOBank, HardwareAddress,
+    IoExpander, NoDelay, Pca9535Cached, Pca9535Immediate, PinId, PinState, Register, RetryPolicy,
+    StandardExpanderInterface,
+};
+
+const ADDR: u8 = 33;
+
+#[test]
+fn error_kind_and_display() {
+    let device = SimDevice::new(ADDR);
+    let mut expander = Pca9535Immediate::new(device, ADDR + 1);
+
+    let error = expander
+        .write_byte(Register::OutputPort0, 0x00)
+        .unwrap_err();
+
+    assert_eq!(
+        error.i2c_error_kind(),
+        Some(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address))
+    );
+    assert!(error.to_string().starts_with("I2C write failed"));
+
+    let error: ExpanderError<ErrorKind> = ExpanderError::CacheMismatch {
+        register: Register::OutputPort1,
+        cached: 0xFF,
+        actual: 0x0F,
+    };
+
+    assert_eq!(error.i2c_error_kind(), None);
+    assert_eq!(
+        error.to_string(),
+        "cached value 0xFF of register OutputPort1 differs from device value 0x0F"
+    );
+}
+
+#[test]
+fn invalid_address_and_pin() {
+    let device = SimDevice::new(ADDR);
+
+    assert!(matches!(
+        Pca9535Immediate::try_new(device.clone(), 40),
+        Err(ExpanderError::InvalidAddress(40))
+    ));
+    assert!(matches!(
+        Pca9535Cached::new(device.clone(), 31, device.interrupt_pin(), false),
+        Err(ExpanderError::InvalidAddress(31))
+    ));
+
+    let mut expander = Pca9535Immediate::try_new(device.clone(), ADDR).unwrap();
+    let transactions = device.transaction_count();
+
+    assert!(matches!(
+        expander.pin_set_high(GPIOBank::Bank0, 8),
+        Err(ExpanderError::InvalidPin(8))
+    ));
+    assert!(matches!(
+        expander.pin_is_high(GPIOBank::Bank1, 9),
+        Err(ExpanderError::InvalidPin(9))
+    ));
+    assert_eq!(device.transaction_count(), transactions);
+}
+
+#[test]
+fn hardware_address_and_pin_id() {
+    let address = HardwareAddress::new(false, false, true);
+
+    assert_eq!(address.address(), ADDR);
+    assert_eq!(HardwareAddress::from_address(ADDR), Some(address));
+    assert_eq!(HardwareAddress::from_address(40), None);
+    assert_eq!(u8::from(HardwareAddress::new(true, true, true)), 39);
+
+    let pin = PinId::new(GPIOBank::Bank1, 3).unwrap();
+
+    assert_eq!(pin.index(), 11);
+    assert_eq!(pin.pin(), 3);
+    assert!(matches!(pin.bank(), GPIOBank::Bank1));
+    assert_eq!(pin.mask(), 0x0800);
+    assert_eq!(PinId::from_index(11), Some(pin));
+    assert_eq!(PinId::new(GPIOBank::Bank0, 8), None);
+    assert_eq!(PinId::from_index(16), None);
+
+    let device = SimDevice::new(ADDR);
+    let mut expander = Pca9535Immediate::from_hardware_address(device.clone(), address);
+
+    expander.pin_into_output(GPIOBank::Bank1, 3).unwrap();
+
+    let io_expander: IoExpander<_, _, Mutex<_>> = IoExpander::new(expander);
+    let mut output = ExpanderOutputPin::from_pin_id(&io_expander, pin, PinState::Low).unwrap();
+
+    assert_eq!(device.pin_level(GPIOBank::Bank1, 3), PinState::Low);
+
+    output.set_high().unwrap();
+
+    assert_eq!(device.pin_level(GPIOBank::Bank1, 3), PinState::High);
+
+    let mut input =
+        ExpanderInputPin::from_pin_id(&io_expander, PinId::from_index(2).unwrap()).unwrap();
+    device.drive_pin(GPIOBank::Bank0, 2, PinState::High);
+
+    assert!(input.is_high().unwrap());
+}
+
+#[test]
+fn chip_variants() {
+    let device = SimDevice::new(116);
+
+    assert!(matches!(
+        Pca9535Immediate::with_chip(device.clone(), ADDR, Pca9539),
+        Err(ExpanderError::InvalidAddress(ADDR))
+    ));
+
+    let mut expander = Pca9535Immediate::with_chip(device.clone(), 116, Pca9539).unwrap();
+
+    expander.pin_into_output(GPIOBank::Bank1, 0).unwrap();
+    assert_eq!(device.register(Register::ConfigurationPort1), 0xFE);
+    assert!(Pca9539::is_valid_address(119));
+    assert!(!Pca9539::is_valid_address(120));
+}
+
+#[test]
+fn reset() {
+    let device = SimDevice::new(116);
+    let mut expander = Pca9535Immediate::with_chip(device.clone(), 116, Pca9539)
+        .unwrap()
+        .with_reset_pin(device.reset_pin())
+        .unwrap();
+
+    expander.pin_into_output(GPIOBank::Bank1, 7).unwrap();
+    expander.reset(&mut NoDelay).unwrap();
+
+    assert_eq!(device.register(Register::ConfigurationPort1), 0xFF);
+}
+
+#[test]
+fn retry_policy() {
+    let device = SimDevice::new(ADDR);
+    let failures = Rc::new(Cell::new(0));
+    let delays = Rc::new(Cell::new(0));
+    let bus = FlakyBus {
+        device: device.clone(),
+        failures: failures.clone(),
+        error: ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data),
+    };
+
+    let mut expander = Pca9535Immediate::new(bus, ADDR)
+        .with_retry_policy(RetryPolicy::new(3).with_delay(CountingDelay(delays.clone()), 50));
+
+    failures.set(2);
+    expander.pin_into_output(GPIOBank::Bank0, 1).unwrap();
+
+    assert_eq!(device.register(Register::ConfigurationPort0), 0xFD);
+    assert_eq!(delays.get(), 2);
+
+    failures.set(3);
+
+    assert!(matches!(
+        expander.pin_set_low(GPIOBank::Bank0, 1),
+        Err(ExpanderError::WriteReadError(ErrorKind::NoAcknowledge(
+            NoAcknowledgeSource::Data
+        )))
+    ));
+    assert_eq!(failures.get(), 0);
+    assert_eq!(delays.get(), 4);
+
+    // Missing acknowledges are not retried by this policy.
+    let mut expander =
+        expander.with_retry_policy(RetryPolicy::new(3).retry_on(|kind| kind == ErrorKind::Bus));
+
+    failures.set(1);
+
+    assert!(expander.pin_is_high(GPIOBank::Bank0, 0).is_err());
+    assert_eq!(failures.get(), 0);
+}
+
+#[test]
+fn write_verification() {
+    let device = SimDevice::new(ADDR);
+    let bus = StuckBitBus {
+        device: device.clone(),
+        stuck: 0x01,
+    };
+    let mut expander = Pca9535Immediate::new(bus, ADDR);
+
+    // Without verification, the failed write goes unnoticed.
+    expander.pin_into_output(GPIOBank::Bank0, 0).unwrap();
+
+    assert_eq!(device.register(Register::ConfigurationPort0), 0xFF);
+
+    expander.set_write_verification(true);
+
+    assert!(matches!(
+        expander.pin_into_output(GPIOBank::Bank0, 0),
+        Err(ExpanderError::WriteVerifyMismatch {
+            register: Register::ConfigurationPort0,
+            expected: 0xFE,
+            actual: 0xFF
+        })
+    ));
+    assert!(matches!(
+        expander.write_halfword(Register::OutputPort1, 0x1334),
+        Err(ExpanderError::WriteVerifyMismatch {
+            register: Register::OutputPort0,
+            expected: 0x34,
+            actual: 0x35
+        })
+    ));
+
+    // Writes not affected by the stuck bit and polarity inversion writes pass.
+    expander.pin_into_output(GPIOBank::Bank1, 3).unwrap();
+    expander
+        .write_byte(Register::PolarityInversionPort0, 0x02)
+        .unwrap();
+}
+
+#[cfg(test)]
+mod standard {
+    use super::ADDR;
+
+    use pca9535::sim::SimDevice;
+    use pca9535::{GPIOBank, Pca9535Immediate, PinState, Register, StandardExpanderInterface};
+
+    #[test]
+    fn immediate_input_output() {
+        let device = SimDevice::new(ADDR);
+        let mut expander = Pca9535Immediate::new(device.clone(), ADDR);
+
+        expander.pin_into_input(GPIOBank::Bank0, 2).unwrap();
+        device.drive_pin(GPIOBank::Bank0, 2, PinState::High);
+
+        assert!(expander.pin_is_high(GPIOBank::Bank0, 2).unwrap());
+
+        expander.pin_inverse_polarity(GPIOBank::Bank0, 2).unwrap();
+
+        assert!(expander.pin_is_low(GPIOBank::Bank0, 2).unwrap());
+
+        expander.pin_into_output(GPIOBank::Bank1, 6).unwrap();
+        expander.pin_set_low(GPIOBank::Bank1, 6).unwrap();
+
+        assert_eq!(device.pin_level(GPIOBank::Bank1, 6), PinState::Low);
+
+        expander.pin_set_high(GPIOBank::Bank1, 6).unwrap();
+
+        assert_eq!(device.pin_level(GPIOBank::Bank1, 6), PinState::High);
+    }
+
+    #[test]
+    fn toggle_and_output_state() {
+        let device = SimDevice::new(ADDR);
+        let mut expander = Pca9535Immediate::new(device.clone(), ADDR);
+
+        expander.pin_into_output(GPIOBank::Bank0, 6).unwrap();
+        expander.pin_set_low(GPIOBank::Bank0, 6).unwrap();
+
+        assert!(expander.pin_is_set_low(GPIOBank::Bank0, 6).unwrap());
+
+        expander.pin_toggle(GPIOBank::Bank0, 6).unwrap();
+
+        assert!(expander.pin_is_set_high(GPIOBank::Bank0, 6).unwrap());
+        assert_eq!(device.pin_level(GPIOBank::Bank0, 6), PinState::High);
+
+        expander.pin_toggle(GPIOBank::Bank0, 6).unwrap();
+
+        assert!(!expander.pin_is_set_high(GPIOBank::Bank0, 6).unwrap());
+        assert_eq!(device.register(Register::OutputPort0), 0xBF);
+    }
+
+    #[test]
+    fn bulk_operations() {
+        let device = SimDevice::new(ADDR);
+        let mut expander = Pca9535Immediate::new(device.clone(), ADDR);
+
+        expander.pins_into_output(0x81F0).unwrap();
+
+        assert_eq!(device.register(Register::ConfigurationPort0), 0x0F);
+        assert_eq!(device.register(Register::ConfigurationPort1), 0x7E);
+
+        let transactions = device.transaction_count();
+
+        expander.write_outputs(0x8010).unwrap();
+
+        assert_eq!(device.transaction_count(), transactions + 1);
+        assert_eq!(device.register(Register::OutputPort0), 0x10);
+        assert_eq!(device.register(Register::OutputPort1), 0x80);
+
+        expander.pins_set_high(0x0120).unwrap();
+        expander.pins_set_low(0x8000).unwrap();
+        expander.pins_toggle(0x0030).unwrap();
+
+        assert_eq!(device.register(Register::OutputPort0), 0x00);
+        assert_eq!(device.register(Register::OutputPort1), 0x01);
+
+        expander.port_write(GPIOBank::Bank1, 0x81).unwrap();
+
+        assert_eq!(device.pin_level(GPIOBank::Bank1, 7), PinState::High);
+
+        expander.pins_into_input(0x0100).unwrap();
+        device.drive_pin(GPIOBank::Bank1, 0, PinState::Low);
+        device.drive_pin(GPIOBank::Bank0, 1, PinState::High);
+
+        let transactions = device.transaction_count();
+
+        assert_eq!(expander.read_inputs().unwrap(), 0x8002);
+        assert_eq!(device.transaction_count(), transactions + 1);
+        assert_eq!(expander.port_read(GPIOBank::Bank1).unwrap(), 0x80);
+    }
+}
+
+#[cfg(test)]
+mod pin {
+    use std::cell::RefCell;
+    use std::sync::{Arc, Mutex, RwLock};
+
+    use super::ADDR;
+
+    use hal::digital::{InputPin, OutputPin, StatefulOutputPin};
+    use pca9535::sim::SimDevice;
+    use pca9535::{
+        ArcInputPin, ArcOutputPin, Direction, ExpanderError, ExpanderFlexPin, ExpanderInputPin,
+        ExpanderMutex, ExpanderOutputPin, GPIOBank, HandleOutputPin, IoExpander, LockError,
+        Pca9535Immediate, PinId, PinState, Polarity, Register,
+    };
+
+    #[test]
+    fn stateful_output_pin() {
+        let device = SimDevice::new(ADDR);
+        let expander = Pca9535Immediate::new(device.clone(), ADDR);
+        let io_expander: IoExpander<_, _, Mutex<_>> = IoExpander::new(expander);
+
+        let mut output =
+            ExpanderOutputPin::new(&io_expander, GPIOBank::Bank0, 1, PinState::Low).unwrap();
+
+        assert!(output.is_set_low().unwrap());
+
+        output.toggle().unwrap();
+
+        assert!(output.is_set_high().unwrap());
+        assert_eq!(device.pin_level(GPIOBank::Bank0, 1), PinState::High);
+
+        output.toggle().unwrap();
+
+        assert!(!output.is_set_high().unwrap());
+        assert_eq!(device.pin_level(GPIOBank::Bank0, 1), PinState::Low);
+    }
+
+    #[test]
+    fn transaction_commits_once_per_bank() {
+        let device = SimDevice::new(ADDR);
+        device.set_register(Register::ConfigurationPort0, 0x00);
+        device.set_register(Register::ConfigurationPort1, 0x00);
+        device.set_register(Register::OutputPort0, 0x0F);
+
+        let expander = Pca9535Immediate::new(device.clone(), ADDR);
+        let io_expander: IoExpander<_, _, Mutex<_>> = IoExpander::new(expander);
+
+        let transactions = device.transaction_count();
+
+        io_expander
+            .transaction(|transaction| {
+                transaction
+                    .set_high(GPIOBank::Bank0, 7)
+                    .set_low(GPIOBank::Bank0, 0)
+                    .set_high(GPIOBank::Bank0, 1)
+                    .set_state(GPIOBank::Bank0, 1, PinState::Low);
+            })
+            .unwrap();
+
+        // One read and one write of output port 0
+        assert_eq!(device.transaction_count(), transactions + 2);
+        assert_eq!(device.register(Register::OutputPort0), 0x8C);
+        assert_eq!(device.register(Register::OutputPort1), 0xFF);
+
+        let transactions = device.transaction_count();
+
+        let result = io_expander
+            .transaction(|transaction| {
+                transaction
+                    .set_high(GPIOBank::Bank0, 0)
+                    .set_low(GPIOBank::Bank1, 7);
+                42
+            })
+            .unwrap();
+
+        assert_eq!(result, 42);
+        assert_eq!(device.transaction_count(), transactions + 2);
+        assert_eq!(device.register(Register::OutputPort0), 0x8D);
+        assert_eq!(device.register(Register::OutputPort1), 0x7F);
+
+        let transactions = device.transaction_count();
+
+        io_expander.transaction(|_| {}).unwrap();
+
+        assert_eq!(device.transaction_count(), transactions);
+    }
+
+    #[test]
+    fn transaction_rejects_invalid_pin() {
+        let device = SimDevice::new(ADDR);
+        device.set_register(Register::ConfigurationPort0, 0x00);
+
+        let expander = Pca9535Immediate::new(device.clone(), ADDR);
+        let io_expander: IoExpander<_, _, Mutex<_>> = IoExpander::new(expander);
+
+        let transactions = device.transaction_count();
+
+        let result = io_expander.transaction(|transaction| {
+            transaction
+                .set_low(GPIOBank::Bank0, 0)
+                .set_high(GPIOBank::Bank0, 8)
+                .set_high(GPIOBank::Bank1, 9);
+        });
+
+        assert!(matches!(result, Err(ExpanderError::InvalidPin(8))));
+        assert_eq!(device.transaction_count(), transactions);
+        assert_eq!(device.register(Register::OutputPort0), 0xFF);
+
+        io_expander
+            .transaction(|transaction| {
+                transaction.set_pin(PinId::new(GPIOBank::Bank0, 0).unwrap(), PinState::Low);
+            })
+            .unwrap();
+
+        assert_eq!(device.register(Register::OutputPort0), 0xFE);
+    }
+
+    #[test]
+    fn split_pins_typestate() {
+        let device = SimDevice::new(ADDR);
+        device.set_register(Register::ConfigurationPort0, 0x00);
+
+        let expander = Pca9535Immediate::new(device.clone(), ADDR);
+        let mut io_expander: IoExpander<_, _, Mutex<_>> = IoExpander::new(expander);
+
+        let pins = io_expander.split().unwrap();
+
+        assert_eq!(device.register(Register::ConfigurationPort0), 0xFF);
+        assert_eq!(device.register(Register::ConfigurationPort1), 0xFF);
+
+        let mut output = pins.io1_2.into_output(PinState::Low).unwrap();
+
+        assert_eq!(device.register(Register::ConfigurationPort1), 0xFB);
+        assert_eq!(device.pin_level(GPIOBank::Bank1, 2), PinState::Low);
+
+        output.set_high().unwrap();
+
+        assert_eq!(device.pin_level(GPIOBank::Bank1, 2), PinState::High);
+
+        let mut input = output.into_input().unwrap();
+        device.drive_pin(GPIOBank::Bank1, 2, PinState::Low);
+
+        assert_eq!(device.register(Register::ConfigurationPort1), 0xFF);
+        assert!(input.is_low().unwrap());
+
+        let mut other = pins.io0_7;
+        device.drive_pin(GPIOBank::Bank0, 7, PinState::High);
+
+        assert!(other.is_high().unwrap());
+    }
+
+    fn toggle_output_pin<Em>(device: &SimDevice)
+    where
+        Em: ExpanderMutex<Pca9535Immediate<SimDevice>>,
+    {
+        let expander = Pca9535Immediate::new(device.clone(), ADDR);
+        let io_expander: IoExpander<_, _, Em> = IoExpander::new(expander);
+
+        let mut output =
+            ExpanderOutputPin::new(&io_expander, GPIOBank::Bank1, 4, PinState::Low).unwrap();
+
+        assert_eq!(device.pin_level(GPIOBank::Bank1, 4), PinState::Low);
+
+        output.toggle().unwrap();
+
+        assert_eq!(device.pin_level(GPIOBank::Bank1, 4), PinState::High);
+
+        let mut input = ExpanderInputPin::new(&io_expander, GPIOBank::Bank1, 4).unwrap();
+
+        device.drive_pin(GPIOBank::Bank1, 4, PinState::Low);
+
+        assert!(input.is_low().unwrap());
+    }
+
+    #[test]
+    fn bare_metal_mutexes() {
+        toggle_output_pin::<RefCell<_>>(&SimDevice::new(ADDR));
+        toggle_output_pin::<critical_section::Mutex<RefCell<_>>>(&SimDevice::new(ADDR));
+        toggle_output_pin::<spin::Mutex<_>>(&SimDevice::new(ADDR));
+    }
+
+    #[test]
+    fn fallible_locking() {
+        toggle_output_pin::<RwLock<_>>(&SimDevice::new(ADDR));
+        toggle_output_pin::<parking_lot::Mutex<_>>(&SimDevice::new(ADDR));
+
+        let device = SimDevice::new(ADDR);
+        let expander = Pca9535Immediate::new(device.clone(), ADDR);
+        let io_expander: IoExpander<_, _, RefCell<_>> = IoExpander::new(expander);
+        let mut output =
+            ExpanderOutputPin::new(&io_expander, GPIOBank::Bank0, 2, PinState::Low).unwrap();
+
+        assert!(matches!(
+            io_expander.with_expander(|_| output.set_high()).unwrap(),
+            Err(ExpanderError::LockError(LockError::WouldBlock))
+        ));
+        assert_eq!(device.pin_level(GPIOBank::Bank0, 2), PinState::Low);
+
+        let expander = Pca9535Immediate::new(device.clone(), ADDR);
+        let io_expander: IoExpander<_, _, Mutex<_>> = IoExpander::new(expander);
+        let mut output =
+            ExpanderOutputPin::new(&io_expander, GPIOBank::Bank0, 2, PinState::High).unwrap();
+
+        std::thread::scope(|scope| {
+            let poisoner = scope.spawn(|| io_expander.with_expander(|_| panic!("poisoned")));
+
+            assert!(poisoner.join().is_err());
+        });
+
+        assert!(matches!(
+            output.set_low(),
+            Err(ExpanderError::LockError(LockError::Poisoned))
+        ));
+        assert!(matches!(
+            io_expander.with_expander(|_| ()),
+            Err(LockError::Poisoned)
+        ));
+        assert_eq!(device.pin_level(GPIOBank::Bank0, 2), PinState::High);
+    }
+
+    #[test]
+    fn owned_pins() {
+        let device = SimDevice::new(ADDR);
+        let expander = Pca9535Immediate::new(device.clone(), ADDR);
+        let io_expander: Arc<IoExpander<_, _, Mutex<_>>> = Arc::new(IoExpander::new(expander));
+
+        let mut output =
+            ArcOutputPin::new(io_expander.clone(), GPIOBank::Bank0, 1, PinState::Low).unwrap();
+        let mut input = ArcInputPin::new(io_expander.clone(), GPIOBank::Bank1, 3).unwrap();
+
+        std::thread::spawn(move || output.set_high().unwrap())
+            .join()
+            .unwrap();
+
+        assert_eq!(device.pin_level(GPIOBank::Bank0, 1), PinState::High);
+
+        device.drive_pin(GPIOBank::Bank1, 3, PinState::High);
+
+        assert!(std::thread::spawn(move || input.is_high().unwrap())
+            .join()
+            .unwrap());
+        assert_eq!(Arc::strong_count(&io_expander), 1);
+
+        let mut output = ArcInputPin::new(io_expander, GPIOBank::Bank1, 2)
+            .unwrap()
+            .into_output(PinState::High)
+            .unwrap();
+
+        assert_eq!(device.pin_level(GPIOBank::Bank1, 2), PinState::High);
+
+        output.toggle().unwrap();
+
+        assert_eq!(device.pin_level(GPIOBank::Bank1, 2), PinState::Low);
+
+        let expander = Pca9535Immediate::new(device.clone(), ADDR);
+        let io_expander: &'static IoExpander<_, _, Mutex<_>> =
+            Box::leak(Box::new(IoExpander::new(expander)));
+        let mut output: HandleOutputPin<_, &'static _> =
+            HandleOutputPin::new(io_expander, GPIOBank::Bank0, 7, PinState::Low).unwrap();
+
+        std::thread::spawn(move || output.set_high().unwrap())
+            .join()
+            .unwrap();
+
+        assert_eq!(device.pin_level(GPIOBank::Bank0, 7), PinState::High);
+    }
+
+    #[test]
+    fn flex_pin() {
+        let device = SimDevice::new(ADDR);
+        let expander = Pca9535Immediate::new(device.clone(), ADDR);
+        let io_expander: IoExpander<_, _, Mutex<_>> = IoExpander::new(expander);
+
+        let mut pin = ExpanderFlexPin::new(&io_expander, GPIOBank::Bank1, 6).unwrap();
+
+        assert_eq!(pin.direction(), Direction::Input);
+        assert!(matches!(
+            pin.set_high(),
+            Err(ExpanderError::DirectionMismatch)
+        ));
+        assert!(matches!(
+            pin.is_set_high(),
+            Err(ExpanderError::DirectionMismatch)
+        ));
+
+        device.drive_pin(GPIOBank::Bank1, 6, PinState::High);
+
+        assert!(pin.is_high().unwrap());
+
+        pin.set_as_output(PinState::Low).unwrap();
+
+        assert_eq!(pin.direction(), Direction::Output);
+        assert_eq!(device.pin_level(GPIOBank::Bank1, 6), PinState::Low);
+        assert!(matches!(
+            pin.is_low(),
+            Err(ExpanderError::DirectionMismatch)
+        ));
+
+        pin.toggle().unwrap();
+
+        assert!(pin.is_set_high().unwrap());
+        assert_eq!(device.pin_level(GPIOBank::Bank1, 6), PinState::High);
+
+        pin.set_low().unwrap();
+
+        assert_eq!(device.pin_level(GPIOBank::Bank1, 6), PinState::Low);
+
+        pin.set_as_input().unwrap();
+        pin.set_polarity(Polarity::Inverse).unwrap();
+        device.drive_pin(GPIOBank::Bank1, 6, PinState::Low);
+
+        assert!(pin.is_high().unwrap());
+
+        let output =
+            ExpanderOutputPin::new(&io_expander, GPIOBank::Bank0, 0, PinState::High).unwrap();
+        let mut pin = output.into_flex();
+
+        assert_eq!(pin.direction(), Direction::Output);
+        assert_eq!(
+            ExpanderFlexPin::new(&io_expander, GPIOBank::Bank0, 0)
+                .unwrap()
+                .direction(),
+            Direction::Output
+        );
+
+        pin.set_low().unwrap();
+
+        assert_eq!(device.pin_level(GPIOBank::Bank0, 0), PinState::Low);
+        assert!(matches!(
+            ExpanderFlexPin::new(&io_expander, GPIOBank::Bank0, 8),
+            Err(ExpanderError::InvalidPin(8))
+        ));
+    }
+}
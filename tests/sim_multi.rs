@@ -0,0 +1,201 @@
+mod common {
+    pub mod foreign_device;
+    pub mod interleaving_mutex;
+    pub mod sim_bus;
+}
+
+use std::sync::Mutex;
+
+use common::foreign_device::ForeignDevice;
+use common::interleaving_mutex::InterleavingMutex;
+use common::sim_bus::SimBus;
+
+use hal::digital::{InputPin, OutputPin, StatefulOutputPin};
+use hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use pca9535::sim::SimDevice;
+use pca9535::{
+    probe, scan, ExpanderError, ExpanderOutputPin, GPIOBank, MultiExpander, Pca9535Immediate,
+    PinState, Presence, Register, StandardExpanderInterface,
+};
+
+const ADDR: u8 = 33;
+
+#[test]
+fn multi_expander() {
+    let devices = [SimDevice::new(32), SimDevice::new(34), SimDevice::new(39)];
+    let expanders: MultiExpander<_, Mutex<_>> = MultiExpander::new(SimBus(devices.to_vec()));
+
+    assert_eq!(expanders.scan().unwrap(), 0b1000_0101);
+
+    assert!(expanders.device(8).is_none());
+    assert_eq!(expanders.device(7).unwrap().address(), 39);
+
+    // Pin 42 is pin 10 of device 2, which is pin 2 of its bank 1.
+    expanders.pin_into_output(42).unwrap();
+    expanders.pin_set_low(42).unwrap();
+
+    assert_eq!(devices[1].register(Register::ConfigurationPort1), 0xFB);
+    assert_eq!(devices[1].pin_level(GPIOBank::Bank1, 2), PinState::Low);
+    assert!(!expanders.pin_is_set_high(42).unwrap());
+    assert_eq!(devices[0].register(Register::ConfigurationPort1), 0xFF);
+
+    devices[2].drive_pin(GPIOBank::Bank0, 7, PinState::High);
+
+    assert!(expanders.pin_is_high(119).unwrap());
+    assert!(expanders.pin_is_low(118).unwrap());
+
+    assert!(matches!(
+        expanders.pin_set_high(128),
+        Err(ExpanderError::InvalidPin(128))
+    ));
+    assert!(matches!(
+        expanders.pin_set_high(20),
+        Err(ExpanderError::WriteReadError(ErrorKind::NoAcknowledge(
+            NoAcknowledgeSource::Address
+        )))
+    ));
+
+    let [mut device_0, ..] = expanders.devices();
+
+    device_0.pin_into_output(GPIOBank::Bank0, 3).unwrap();
+    device_0.pin_set_high(GPIOBank::Bank0, 3).unwrap();
+
+    assert_eq!(devices[0].pin_level(GPIOBank::Bank0, 3), PinState::High);
+
+    let mut led = ExpanderOutputPin::new(&device_0, GPIOBank::Bank0, 4, PinState::High).unwrap();
+
+    led.set_low().unwrap();
+
+    assert_eq!(devices[0].pin_level(GPIOBank::Bank0, 4), PinState::Low);
+    assert!(!expanders.pin_is_set_high(4).unwrap());
+}
+
+#[test]
+fn multi_expander_flat_hal_pins() {
+    let devices = [SimDevice::new(32), SimDevice::new(34)];
+    let expanders: MultiExpander<_, Mutex<_>> = MultiExpander::new(SimBus(devices.to_vec()));
+
+    // Pin 42 is pin 2 of bank 1 of device 2, pin 5 is pin 5 of bank 0 of device 0.
+    let mut output = expanders.output_pin(42, PinState::High).unwrap();
+    let mut input = expanders.input_pin(5).unwrap();
+
+    assert_eq!(devices[1].register(Register::ConfigurationPort1), 0xFB);
+    assert_eq!(devices[1].pin_level(GPIOBank::Bank1, 2), PinState::High);
+
+    output.set_low().unwrap();
+
+    assert_eq!(devices[1].pin_level(GPIOBank::Bank1, 2), PinState::Low);
+    assert!(input.is_low().unwrap());
+
+    devices[0].drive_pin(GPIOBank::Bank0, 5, PinState::High);
+
+    assert!(input.is_high().unwrap());
+    assert!(matches!(
+        expanders.input_pin(128),
+        Err(ExpanderError::InvalidPin(128))
+    ));
+}
+
+#[test]
+fn multi_expander_atomic_pin_updates() {
+    let device = SimDevice::new(32);
+    let expanders: MultiExpander<_, InterleavingMutex> =
+        MultiExpander::new(SimBus(vec![device.clone()]));
+
+    expanders.pin_set_low(0).unwrap();
+
+    assert_eq!(device.register(Register::OutputPort0), 0xFC);
+
+    expanders.pin_toggle(1).unwrap();
+    expanders.pin_into_output(1).unwrap();
+
+    assert_eq!(device.register(Register::OutputPort0), 0xFE);
+    assert_eq!(device.register(Register::ConfigurationPort0), 0xFD);
+}
+
+#[test]
+fn multi_expander_atomic_hal_pins() {
+    let device = SimDevice::new(32);
+    let expanders: MultiExpander<_, InterleavingMutex<5>> =
+        MultiExpander::new(SimBus(vec![device.clone()]));
+    let [device_0, ..] = expanders.devices();
+
+    let mut output = ExpanderOutputPin::new(&device_0, GPIOBank::Bank0, 0, PinState::Low).unwrap();
+
+    // The other user interleaves during or after the toggles, which must not undo its change.
+    output.toggle().unwrap();
+    output.toggle().unwrap();
+    output.toggle().unwrap();
+
+    assert_eq!(device.register(Register::OutputPort0), 0xFD);
+}
+
+#[test]
+fn probe_and_scan() {
+    let mut device = SimDevice::new(ADDR);
+
+    assert_eq!(
+        probe(&mut device, ADDR).unwrap(),
+        Presence::Expander {
+            configuration: 0xFFFF
+        }
+    );
+    assert_eq!(probe(&mut device, ADDR + 1).unwrap(), Presence::Absent);
+
+    let mut expander = Pca9535Immediate::new(device.clone(), ADDR);
+
+    expander.pin_into_output(GPIOBank::Bank1, 0).unwrap();
+
+    assert_eq!(
+        expander.probe().unwrap(),
+        Presence::Expander {
+            configuration: 0xFFFE
+        }
+    );
+
+    assert_eq!(
+        probe(&mut ForeignDevice(None), ADDR).unwrap(),
+        Presence::Unrecognized
+    );
+    assert_eq!(
+        probe(
+            &mut ForeignDevice(Some(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data))),
+            ADDR
+        )
+        .unwrap(),
+        Presence::Unrecognized
+    );
+    assert_eq!(
+        probe(
+            &mut ForeignDevice(Some(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Unknown))),
+            ADDR
+        )
+        .unwrap(),
+        Presence::Absent
+    );
+    assert!(matches!(
+        probe(&mut ForeignDevice(Some(ErrorKind::Bus)), ADDR),
+        Err(ExpanderError::WriteReadError(ErrorKind::Bus))
+    ));
+
+    let devices = [SimDevice::new(32), SimDevice::new(38)];
+    let presences = scan(&mut SimBus(devices.to_vec())).unwrap();
+
+    assert!(presences[0].is_expander());
+    assert!(presences[6].is_expander());
+    assert_eq!(
+        presences
+            .iter()
+            .filter(|presence| presence.is_expander())
+            .count(),
+        2
+    );
+
+    let expanders: MultiExpander<_, Mutex<_>> = MultiExpander::new(SimBus(devices.to_vec()));
+
+    assert_eq!(expanders.scan_presence().unwrap(), presences);
+    assert_eq!(
+        expanders.device(1).unwrap().probe().unwrap(),
+        Presence::Absent
+    );
+}
@@ -0,0 +1,66 @@
+use std::sync::Mutex;
+
+use hal::digital::{InputPin, OutputPin};
+use pca9535::sim::SimDevice;
+use pca9535::{
+    ExpanderError, GPIOBank, IoExpander, Pca9554Immediate, PinState, Register,
+    StandardExpanderInterface,
+};
+
+const ADDR: u8 = 33;
+
+#[test]
+fn eight_bit_expander() {
+    let device = SimDevice::new_pca9554(ADDR);
+    let mut expander = Pca9554Immediate::new(device.clone(), ADDR);
+
+    expander.pins_into_output(0x000F).unwrap();
+    expander.write_outputs(0x0005).unwrap();
+
+    assert_eq!(device.register(Register::ConfigurationPort0), 0xF0);
+    assert_eq!(device.register(Register::OutputPort0), 0x05);
+    assert!(matches!(
+        expander.write_outputs(0xFF05),
+        Err(ExpanderError::UnsupportedRegister(Register::OutputPort1))
+    ));
+    assert!(matches!(
+        expander.pins_toggle(0x0100),
+        Err(ExpanderError::UnsupportedRegister(Register::OutputPort1))
+    ));
+    assert_eq!(device.register(Register::OutputPort0), 0x05);
+
+    expander.pins_toggle(0x0001).unwrap();
+
+    assert_eq!(device.register(Register::OutputPort0), 0x04);
+
+    expander.inverse_polarity().unwrap();
+
+    assert_eq!(device.register(Register::PolarityInversionPort0), 0xFF);
+
+    expander.normal_polarity().unwrap();
+
+    assert_eq!(device.register(Register::PolarityInversionPort0), 0x00);
+    assert_eq!(expander.read_inputs().unwrap() & 0xFF00, 0x0000);
+    assert!(matches!(
+        expander.pin_set_high(GPIOBank::Bank1, 0),
+        Err(ExpanderError::UnsupportedRegister(Register::OutputPort1))
+    ));
+
+    let mut io_expander: IoExpander<_, _, Mutex<_>> = IoExpander::new(expander);
+    let pins = io_expander.split().unwrap();
+
+    assert_eq!(device.register(Register::ConfigurationPort0), 0xFF);
+
+    let mut output = pins.io0_3.into_output(PinState::High).unwrap();
+
+    assert_eq!(device.pin_level(GPIOBank::Bank0, 3), PinState::High);
+
+    output.set_low().unwrap();
+
+    assert_eq!(device.pin_level(GPIOBank::Bank0, 3), PinState::Low);
+
+    let mut input = pins.io0_6;
+    device.drive_pin(GPIOBank::Bank0, 6, PinState::High);
+
+    assert!(input.is_high().unwrap());
+}
@@ -0,0 +1,117 @@
+use pca9535::sim::SimDevice;
+use pca9535::{
+    AgileExpanderInterface, DriveStrength, ExtendedRegister, GPIOBank, OutputMode, Pcal9535a,
+    PinState, Pull, StandardExpanderInterface,
+};
+
+const ADDR: u8 = 33;
+
+#[test]
+fn pull_resistors() {
+    let device = SimDevice::new_pcal9535a(ADDR);
+    let mut expander = Pcal9535a::new(device.clone(), ADDR);
+
+    device.release_pin(GPIOBank::Bank0, 1);
+
+    assert!(expander.pin_is_low(GPIOBank::Bank0, 1).unwrap());
+
+    expander.pin_set_pull(GPIOBank::Bank0, 1, Pull::Up).unwrap();
+
+    assert!(expander.pin_is_high(GPIOBank::Bank0, 1).unwrap());
+
+    expander
+        .pin_set_pull(GPIOBank::Bank0, 1, Pull::Down)
+        .unwrap();
+
+    assert!(expander.pin_is_low(GPIOBank::Bank0, 1).unwrap());
+    assert_eq!(
+        device.extended_register(ExtendedRegister::PullEnable0),
+        0x02
+    );
+    assert_eq!(
+        device.extended_register(ExtendedRegister::PullSelection0),
+        0xFD
+    );
+
+    expander
+        .pin_set_pull(GPIOBank::Bank0, 1, Pull::None)
+        .unwrap();
+
+    assert_eq!(
+        device.extended_register(ExtendedRegister::PullEnable0),
+        0x00
+    );
+}
+
+#[test]
+fn interrupt_mask_and_status() {
+    let device = SimDevice::new_pcal9535a(ADDR);
+    let mut expander = Pcal9535a::new(device.clone(), ADDR);
+
+    // All interrupts are masked after power-on
+    device.drive_pin(GPIOBank::Bank1, 4, PinState::High);
+
+    assert!(!device.interrupt_asserted());
+    assert_eq!(expander.interrupt_status().unwrap(), 0x0000);
+
+    expander.pin_unmask_interrupt(GPIOBank::Bank1, 4).unwrap();
+
+    assert!(device.interrupt_asserted());
+    assert_eq!(expander.interrupt_status().unwrap(), 0x1000);
+
+    assert!(expander.pin_is_high(GPIOBank::Bank1, 4).unwrap());
+    assert!(!device.interrupt_asserted());
+    assert_eq!(expander.interrupt_status().unwrap(), 0x0000);
+
+    expander.pin_mask_interrupt(GPIOBank::Bank1, 4).unwrap();
+    device.drive_pin(GPIOBank::Bank1, 4, PinState::Low);
+
+    assert!(!device.interrupt_asserted());
+}
+
+#[test]
+fn drive_strength_latch_and_output_mode() {
+    let device = SimDevice::new_pcal9535a(ADDR);
+    let mut expander = Pcal9535a::new(device.clone(), ADDR);
+
+    expander
+        .pin_set_drive_strength(GPIOBank::Bank1, 5, DriveStrength::Quarter)
+        .unwrap();
+    expander
+        .pin_set_drive_strength(GPIOBank::Bank0, 2, DriveStrength::Half)
+        .unwrap();
+
+    assert_eq!(
+        device.extended_register(ExtendedRegister::OutputDriveStrength1B),
+        0xF3
+    );
+    assert_eq!(
+        device.extended_register(ExtendedRegister::OutputDriveStrength0A),
+        0xDF
+    );
+
+    expander.pin_enable_input_latch(GPIOBank::Bank0, 7).unwrap();
+
+    assert_eq!(
+        device.extended_register(ExtendedRegister::InputLatch0),
+        0x80
+    );
+
+    expander
+        .pin_disable_input_latch(GPIOBank::Bank0, 7)
+        .unwrap();
+
+    assert_eq!(
+        device.extended_register(ExtendedRegister::InputLatch0),
+        0x00
+    );
+
+    expander
+        .set_output_mode(GPIOBank::Bank1, OutputMode::OpenDrain)
+        .unwrap();
+
+    assert_eq!(
+        device.extended_register(ExtendedRegister::OutputPortConfiguration),
+        0x02
+    );
+}